@@ -1,89 +1,257 @@
 use anyhow::{Context, Result};
-use clap::{arg, command, ArgAction, ArgMatches, Command};
-use libsemverator::{
-    range::Range,
-    semver::{bump::SemverComponent, Semver},
-};
+use clap::{arg, builder::PossibleValuesParser, command, Arg, ArgAction, ArgMatches, Command};
+use libsemverator::semver::bump::SemverComponent;
 
 pub fn setup() -> Command {
     command!()
         .subcommand_required(true)
         .arg_required_else_help(true)
+        .arg(
+            arg!(--format <FORMAT> "how to print results")
+                .value_parser(PossibleValuesParser::new(["plain", "json"]))
+                .default_value("plain")
+                .global(true),
+        )
+        .arg(arg!(-q --quiet "print nothing to stdout; the exit status has the answer").global(true))
+        .after_help(EXIT_STATUS)
         // Semver::validate
         .subcommand(
             Command::new("validate")
                 .about("validates a version")
-                .arg(arg!([semver] "the version to validate").value_parser(Semver::parse)),
+                .arg(arg!([semver] "the version to validate").required_unless_present("stdin"))
+                .arg(dialect(&VERSION_DIALECTS))
+                .arg(stdin("each line is a version")),
         )
         // Semver::eq
         .subcommand(
             Command::new("eq")
                 .about("checks if two versions are equal")
-                .arg(arg!([left] "the first version to compare").value_parser(Semver::parse))
-                .arg(arg!([right] "the second version to compare").value_parser(Semver::parse)),
+                .arg(arg!([left] "the first version to compare").required_unless_present("stdin"))
+                .arg(arg!([right] "the second version to compare").required_unless_present("stdin"))
+                .arg(dialect(&VERSION_DIALECTS))
+                .arg(stdin("each line is the versions left out of the command line")),
         )
         // Semver::neq
         .subcommand(
             Command::new("neq")
                 .about("checks if two versions are not equal")
-                .arg(arg!([left] "the first version to compare").value_parser(Semver::parse))
-                .arg(arg!([right] "the second version to compare").value_parser(Semver::parse)),
+                .arg(arg!([left] "the first version to compare").required_unless_present("stdin"))
+                .arg(arg!([right] "the second version to compare").required_unless_present("stdin"))
+                .arg(dialect(&VERSION_DIALECTS))
+                .arg(stdin("each line is the versions left out of the command line")),
         )
         // Semver::gt
         .subcommand(
             Command::new("gt")
                 .about("checks if left > right")
-                .arg(arg!([left] "the first version to compare").value_parser(Semver::parse))
-                .arg(arg!([right] "the second version to compare").value_parser(Semver::parse)),
+                .arg(arg!([left] "the first version to compare").required_unless_present("stdin"))
+                .arg(arg!([right] "the second version to compare").required_unless_present("stdin"))
+                .arg(dialect(&VERSION_DIALECTS))
+                .arg(stdin("each line is the versions left out of the command line")),
         )
         // Semver::lt
         .subcommand(
             Command::new("lt")
                 .about("checks if left < right")
-                .arg(arg!([left] "the first version to compare").value_parser(Semver::parse))
-                .arg(arg!([right] "the second version to compare").value_parser(Semver::parse)),
+                .arg(arg!([left] "the first version to compare").required_unless_present("stdin"))
+                .arg(arg!([right] "the second version to compare").required_unless_present("stdin"))
+                .arg(dialect(&VERSION_DIALECTS))
+                .arg(stdin("each line is the versions left out of the command line")),
         )
         // Semver::bump
         .subcommand(
             Command::new("bump")
                 .about("bumps a version")
-                .arg(arg!([semver] "the version to bump").value_parser(Semver::parse))
+                .arg(arg!([semver] "the version to bump"))
+                .arg(
+                    arg!([bump] "the bump to apply (major|minor|patch|premajor|preminor|prepatch|prerelease|release|letter), or the index of the component to bump")
+                        .value_parser(SemverComponent::parse)
+                        .conflicts_with("calver"),
+                )
+                .arg(arg!(--preid <PREID> "the prerelease identifier to bump to, eg. rc"))
+                .arg(
+                    arg!(--calver <SCHEME> "bump to the next release in a calendar versioning scheme, eg. YYYY.0M.MICRO")
+                        .conflicts_with("preid"),
+                )
                 .arg(
-                    arg!([bump] "the bump to apply (major|minor|patch)")
-                        .value_parser(SemverComponent::parse),
+                    arg!(--date <DATE> "the release date for --calver, as YYYY-MM-DD [default: today]")
+                        .requires("calver"),
                 ),
         )
+        // Ord for Semver
+        .subcommand(
+            Command::new("sort")
+                .about("sorts versions, lowest first")
+                .arg(arg!([semver] "the versions to sort").action(ArgAction::Append))
+                .arg(arg!(-r --reverse "sort highest first"))
+                .arg(stdin("each line is a version to sort")),
+        )
+        // Eq for Semver
+        .subcommand(
+            Command::new("uniq")
+                .about("drops versions equal to one before them")
+                .arg(arg!([semver] "the versions to deduplicate").action(ArgAction::Append))
+                .arg(stdin("each line is a version to deduplicate")),
+        )
+        // Ord for Semver
+        .subcommand(
+            Command::new("compare")
+                .about("prints -1, 0 or 1 as left is less than, equal to or greater than right")
+                .arg(arg!([left] "the first version to compare").required_unless_present("stdin"))
+                .arg(arg!([right] "the second version to compare").required_unless_present("stdin"))
+                .arg(dialect(&VERSION_DIALECTS))
+                .arg(stdin("each line is the versions left out of the command line")),
+        )
+        // repl::run
+        .subcommand(
+            Command::new("repl").about("explores versions and ranges interactively; :help for help"),
+        )
         // Range::validate-range
         .subcommand(
             Command::new("validate-range")
                 .about("validates a range")
-                .arg(arg!([range] "the range to validate").value_parser(Range::parse)),
+                .arg(arg!([range] "the range to validate").required(true)),
         )
         // Range::satisfies
         .subcommand(
             Command::new("satisfies")
                 .about("validates a range satisfies a semver")
-                .arg(arg!([range] "the range to validate").value_parser(Range::parse))
-                .arg(arg!([semver] "the semver to test").value_parser(Semver::parse)),
+                .arg(arg!([range] "the range to validate").required_unless_present("stdin"))
+                .arg(arg!([semver] "the semver to test").required_unless_present("stdin"))
+                .arg(dialect(&RANGE_DIALECTS))
+                .arg(include_prerelease())
+                .arg(stdin("each line is a version, or a range and a version")),
         )
         // Range::max
         .subcommand(
             Command::new("max")
                 .about("maximum version that satisifies a range")
-                .arg(arg!([range] "the range to validate").value_parser(Range::parse))
-                .arg(
-                    arg!([semver] "the semvers to test")
-                        .value_parser(Semver::parse)
-                        .action(ArgAction::Append),
-                ),
+                .arg(arg!([range] "the range to validate").required(true))
+                .arg(arg!([semver] "the semvers to test").action(ArgAction::Append))
+                .arg(dialect(&RANGE_DIALECTS))
+                .arg(include_prerelease())
+                .arg(stdin("each line is a version to test")),
+        )
+        // Range::min
+        .subcommand(
+            Command::new("min")
+                .about("minimum version that satisifies a range")
+                .arg(arg!([range] "the range to validate").required(true))
+                .arg(arg!([semver] "the semvers to test").action(ArgAction::Append))
+                .arg(dialect(&RANGE_DIALECTS))
+                .arg(include_prerelease())
+                .arg(stdin("each line is a version to test")),
+        )
+        // Range::filter
+        .subcommand(
+            Command::new("filter")
+                .about("versions that satisfy a range")
+                .arg(arg!([range] "the range to validate").required(true))
+                .arg(arg!([semver] "the semvers to test").action(ArgAction::Append))
+                .arg(dialect(&RANGE_DIALECTS))
+                .arg(include_prerelease())
+                .arg(stdin("each line is a version to test")),
         )
         // Range::intersect
         .subcommand(
             Command::new("intersect")
                 .about("intersection between two ranges")
-                .arg(arg!([left] "the first range to intersect").value_parser(Range::parse))
-                .arg(arg!([right] "the second range to intersect").value_parser(Range::parse)),
+                .arg(arg!([left] "the first range to intersect"))
+                .arg(arg!([right] "the second range to intersect")),
+        )
+        // Range::union
+        .subcommand(
+            Command::new("union")
+                .about("union of two ranges")
+                .arg(arg!([left] "the first range to unite"))
+                .arg(arg!([right] "the second range to unite")),
+        )
+        // Range::difference
+        .subcommand(
+            Command::new("difference")
+                .about("versions in the first range but not the second")
+                .arg(arg!([left] "the range to subtract from"))
+                .arg(arg!([right] "the range to subtract")),
+        )
+        // Range::complement
+        .subcommand(
+            Command::new("complement")
+                .about("versions outside a range")
+                .arg(arg!([range] "the range to complement")),
+        )
+        // Range::is_subset_of
+        .subcommand(
+            Command::new("subset")
+                .about("checks if every version in left is also in right")
+                .arg(arg!([left] "the candidate subset"))
+                .arg(arg!([right] "the candidate superset")),
         )
+        // Range::is_disjoint
+        .subcommand(
+            Command::new("disjoint")
+                .about("checks if no version is in both ranges")
+                .arg(arg!([left] "the first range to compare"))
+                .arg(arg!([right] "the second range to compare")),
+        )
+}
+
+/// Whether `argv` asks for `--format json`. For when `clap` rejects the rest
+/// of it, and there are no matches to ask.
+pub fn wants_json(argv: &[String]) -> bool {
+    let flags = argv.iter().take_while(|a| *a != "--").collect::<Vec<_>>();
+    flags.iter().any(|a| *a == "--format=json")
+        || flags
+            .windows(2)
+            .any(|w| w[0] == "--format" && w[1] == "json")
+}
+
+/// The subcommand `argv` names, if any, again without asking `clap`.
+pub fn command_name(argv: &[String]) -> Option<String> {
+    let mut words = argv.iter().skip(1);
+    while let Some(word) = words.next() {
+        match word.as_str() {
+            "--format" => {
+                words.next();
+            }
+            flag if flag.starts_with('-') => (),
+            name => return setup().find_subcommand(name).map(|_| word.clone()),
+        }
+    }
+    None
+}
+
+const EXIT_STATUS: &str = "Exit status:
+  0  the command succeeded, or its check held
+  1  the check didn't hold, or no version satisfied the range
+  2  a version, range or other input didn't parse
+  3  the command line was wrong
+  4  a version couldn't be bumped as asked, eg. past the largest number
+
+With --stdin, the worst status of any line.";
+
+// Versioning schemes for commands on versions, and on ranges.
+const VERSION_DIALECTS: [&str; 4] = ["semver", "pep440", "debian", "rpm"];
+const RANGE_DIALECTS: [&str; 2] = ["semver", "pep440"];
+
+fn dialect(dialects: &[&'static str]) -> Arg {
+    arg!(--dialect <DIALECT> "the versioning scheme to use")
+        .value_parser(PossibleValuesParser::new(dialects))
+        .default_value("semver")
+}
+
+// Only for the semver dialect; PEP 440 has its own rules for prereleases.
+fn include_prerelease() -> Arg {
+    arg!(--"include-prerelease" <POLICY> "which prereleases match the range [default: always]")
+        .value_parser(PossibleValuesParser::new(["never", "same-tuple", "always"]))
+}
+
+// Reads the arguments left out of the command line from standard input,
+// one line at a time.
+fn stdin(help: &'static str) -> Arg {
+    Arg::new("stdin")
+        .long("stdin")
+        .help(format!("read from standard input: {help}"))
+        .action(ArgAction::SetTrue)
 }
 
 #[cfg(not(tarpaulin_include))]
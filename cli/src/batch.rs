@@ -0,0 +1,124 @@
+//! `--stdin`: running a command once per line of standard input, so a
+//! caller checking many versions needn't start a process for each.
+
+use std::io::{self, BufRead};
+
+use anyhow::anyhow;
+use clap::ArgMatches;
+use libsemverator::{
+    semver::{
+        dialect::{debian, pep440, rpm},
+        Semver,
+    },
+    Result,
+};
+
+use crate::{
+    args, handle_command,
+    output::{Printer, Status},
+};
+
+/// Runs the command `name`, as given in `argv`, for each line of standard
+/// input, printing each line's result as it goes. A line that fails is
+/// reported and the rest still run. Returns the worst line's status.
+#[cfg(not(tarpaulin_include))]
+pub fn run(argv: &[String], name: &str, matches: &ArgMatches, printer: Printer) -> Status {
+    let base = without_stdin(argv);
+    let mut status = Status::True;
+
+    // These need every version at once, so they run once over all the lines
+    // that parse.
+    if matches!(name, "max" | "min" | "filter" | "sort" | "uniq") {
+        let dialect = matches
+            .try_get_one::<String>("dialect")
+            .ok()
+            .flatten()
+            .map_or("semver", String::as_str);
+        let mut versions = vec![];
+        for (n, line) in lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return status.max(printer.report(name, Some(n), Err(e.into()))),
+            };
+            match parse(dialect, &line) {
+                Ok(()) => versions.push(line),
+                Err(e) => status = status.max(printer.report(name, Some(n), Err(e.into()))),
+            }
+        }
+        let argv = with_fields(&base, versions.iter().map(String::as_str));
+        return status.max(run_one(name, None, &argv, printer));
+    }
+
+    for (n, line) in lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => return status.max(printer.report(name, Some(n), Err(e.into()))),
+        };
+        let argv = with_fields(&base, line.split_whitespace());
+        status = status.max(run_one(name, Some(n), &argv, printer));
+    }
+    status
+}
+
+// The non-blank lines of standard input, numbered from 1.
+#[cfg(not(tarpaulin_include))]
+fn lines() -> impl Iterator<Item = (usize, io::Result<String>)> {
+    io::stdin()
+        .lock()
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.map(|l| l.trim().to_string())))
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.is_empty()))
+}
+
+#[cfg(not(tarpaulin_include))]
+fn run_one(name: &str, line: Option<usize>, argv: &[String], printer: Printer) -> Status {
+    let result = args::setup()
+        .try_get_matches_from(argv)
+        .map_err(|e| anyhow!(usage(&e)))
+        .and_then(|matches| handle_command(matches.subcommand()));
+    printer.report(name, line, result)
+}
+
+/// A `clap` error on one line, without its `error: ` prefix or the usage
+/// after it.
+pub fn usage(e: &clap::Error) -> String {
+    let rendered = e.render().to_string();
+    let message = rendered
+        .lines()
+        .take_while(|l| !l.is_empty())
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ");
+    message
+        .strip_prefix("error: ")
+        .unwrap_or(&message)
+        .to_string()
+}
+
+// Checks that `line` is a version in `dialect`.
+fn parse(dialect: &str, line: &str) -> Result<()> {
+    match dialect {
+        "pep440" => pep440::Version::parse(line).map(drop),
+        "debian" => debian::Version::parse(line).map(drop),
+        "rpm" => rpm::Version::parse(line).map(drop),
+        _ => Semver::parse(line).map(drop),
+    }
+}
+
+/// The command line without `--stdin`.
+pub fn without_stdin(argv: &[String]) -> Vec<String> {
+    argv.iter().filter(|a| *a != "--stdin").cloned().collect()
+}
+
+/// The command line with a line's `fields` as its last positional
+/// arguments. They follow a `--`, so a field like `-1` isn't taken for a
+/// flag.
+pub fn with_fields<'a>(argv: &[String], fields: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut rv = argv.to_vec();
+    if !rv.iter().any(|a| a == "--") {
+        rv.push("--".to_string());
+    }
+    rv.extend(fields.map(String::from));
+    rv
+}
@@ -1,142 +1,505 @@
 extern crate clap;
 
 mod args;
+mod batch;
+mod output;
+mod repl;
 #[cfg(test)]
 mod tests;
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use args::{get_arg, get_arg_vec};
 use clap::ArgMatches;
-use libsemverator::range::Range;
-use libsemverator::semver::Semver;
+use libsemverator::range::{
+    dialect::pep440::SpecifierSet,
+    satisfies::{PrereleasePolicy, SatisfyOptions},
+    Range,
+};
+use libsemverator::semver::{
+    calver::{CalVer, Date},
+    dialect::{debian, pep440::Version, rpm},
+    Semver,
+};
+use output::{Output, Printer, Status};
+use serde_json::{json, Value};
+use std::{cmp::Ordering, collections::HashSet, process};
 
 #[cfg(not(tarpaulin_include))]
-fn main() -> Result<()> {
-    let args = args::setup().get_matches();
+fn main() {
+    let argv = std::env::args_os()
+        .map(|a| a.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    let args = match args::setup().try_get_matches_from(&argv) {
+        Ok(args) => args,
+        // Scripts that asked for JSON get their usage errors in it too.
+        Err(e) if e.use_stderr() && args::wants_json(&argv) => {
+            let printer = Printer {
+                json: true,
+                quiet: argv.iter().any(|a| a == "-q" || a == "--quiet"),
+            };
+            let command = args::command_name(&argv);
+            let status = printer.usage(command.as_deref(), anyhow!(batch::usage(&e)));
+            process::exit(status as i32);
+        }
+        Err(e) => {
+            let _ = e.print();
+            // `--help` and `--version` aren't errors.
+            process::exit(if e.use_stderr() {
+                Status::Usage
+            } else {
+                Status::True
+            } as i32);
+        }
+    };
+    let printer = Printer {
+        json: get_arg::<String>(&args, "format").is_ok_and(|f| f == "json"),
+        quiet: args.get_flag("quiet"),
+    };
+    let Some((name, matches)) = args.subcommand() else {
+        process::exit(Status::Usage as i32);
+    };
 
-    handle_command(args.subcommand())
+    let status = if name == "repl" {
+        repl::run()
+    } else if matches!(matches.try_get_one::<bool>("stdin"), Ok(Some(true))) {
+        batch::run(&argv, name, matches, printer)
+    } else {
+        printer.report(name, None, handle_command(args.subcommand()))
+    };
+    process::exit(status as i32);
 }
 
 // `clap` tested for correctness
 // TODO: factor out as much as possible for testing
 #[cfg(not(tarpaulin_include))]
-fn handle_command(matches: Option<(&str, &ArgMatches)>) -> Result<()> {
+fn handle_command(matches: Option<(&str, &ArgMatches)>) -> Result<Output> {
     use libsemverator::semver::bump::SemverComponent;
 
     match matches {
         // Semver::validate
         Some(("validate", args)) => {
-            let semver = get_arg::<Semver>(args, "semver")?;
-            println!("{} is valid", semver.raw);
-            Ok(())
+            let input = get_arg::<String>(args, "semver")?;
+            let version = match get_arg::<String>(args, "dialect")?.as_str() {
+                "pep440" => output::pep440(&Version::parse(&input)?),
+                "debian" => output::debian(&debian::Version::parse(&input)?),
+                "rpm" => output::rpm(&rpm::Version::parse(&input)?),
+                _ => output::semver(&Semver::parse(&input)?),
+            };
+            Ok(Output::new(
+                format!("{} is valid", raw(&version)),
+                json!({ "version": version }),
+            ))
         }
 
         // Semver::eq
         Some(("eq", args)) => {
-            let left = get_arg::<Semver>(args, "left")?;
-            let right = get_arg::<Semver>(args, "right")?;
+            let (left, right, ordering) = compare(args)?;
+            let fields = comparison(left, right, ordering);
 
-            if left.eq(&right) {
-                println!("versions are equal");
-                Ok(())
+            if ordering.is_eq() {
+                Ok(Output::check(true, "versions are equal", fields))
             } else {
-                bail!("versions are not equal");
+                Ok(Output::check(false, "versions are not equal", fields))
             }
         }
 
         // Semver::neq
         Some(("neq", args)) => {
-            let left = get_arg::<Semver>(args, "left")?;
-            let right = get_arg::<Semver>(args, "right")?;
+            let (left, right, ordering) = compare(args)?;
+            let fields = comparison(left, right, ordering);
 
-            if left.neq(&right) {
-                println!("versions are not equal");
-                Ok(())
+            if ordering.is_ne() {
+                Ok(Output::check(true, "versions are not equal", fields))
             } else {
-                bail!("versions are equal");
+                Ok(Output::check(false, "versions are equal", fields))
             }
         }
 
         // Semver::gt
         Some(("gt", args)) => {
-            let left = get_arg::<Semver>(args, "left")?;
-            let right = get_arg::<Semver>(args, "right")?;
+            let (left, right, ordering) = compare(args)?;
+            let (l, r) = (raw(&left).to_string(), raw(&right).to_string());
+            let fields = comparison(left, right, ordering);
 
-            if left.gt(&right) {
-                println!("{} is greater than {}", left.raw, right.raw);
-                Ok(())
+            if ordering.is_gt() {
+                let plain = format!("{l} is greater than {r}");
+                Ok(Output::check(true, plain, fields))
             } else {
-                bail!("{} is not greater than {}", left.raw, right.raw);
+                let plain = format!("{l} is not greater than {r}");
+                Ok(Output::check(false, plain, fields))
             }
         }
 
         // Semver::lt
         Some(("lt", args)) => {
-            let left = get_arg::<Semver>(args, "left")?;
-            let right = get_arg::<Semver>(args, "right")?;
+            let (left, right, ordering) = compare(args)?;
+            let (l, r) = (raw(&left).to_string(), raw(&right).to_string());
+            let fields = comparison(left, right, ordering);
 
-            if left.lt(&right) {
-                println!("{} is less than {}", left.raw, right.raw);
-                Ok(())
+            if ordering.is_lt() {
+                let plain = format!("{l} is less than {r}");
+                Ok(Output::check(true, plain, fields))
             } else {
-                bail!("{} is not less than {}", left.raw, right.raw);
+                let plain = format!("{l} is not less than {r}");
+                Ok(Output::check(false, plain, fields))
             }
         }
 
         // Semver::bump
         Some(("bump", args)) => {
-            let v_in = get_arg::<Semver>(args, "semver")?;
+            if let Some(scheme) = args.get_one::<String>("calver") {
+                let scheme = CalVer::parse(scheme)?;
+                let date = match args.get_one::<String>("date") {
+                    Some(date) => Date::parse(date)?,
+                    None => Date::today(),
+                };
+                let current = match args.get_one::<String>("semver") {
+                    Some(v) => Some(scheme.validate(v)?),
+                    None => None,
+                };
+                return Ok(version(scheme.next(current.as_ref(), &date)?));
+            }
+
+            let v_in = Semver::parse(&get_arg::<String>(args, "semver")?)?;
             let bump = get_arg::<SemverComponent>(args, "bump")?;
+            let preid = args.get_one::<String>("preid");
 
-            let v_out = v_in.bump(&bump)?;
+            Ok(version(v_in.bump_with(&bump, preid.map(String::as_str))?))
+        }
+
+        // Ord for Semver
+        Some(("sort", args)) => {
+            let mut versions = written(args)?;
+            if args.get_flag("reverse") {
+                versions.sort_by(|(_, a), (_, b)| b.cmp(a));
+            } else {
+                versions.sort_by(|(_, a), (_, b)| a.cmp(b));
+            }
+            Ok(list(versions))
+        }
 
-            println!("{}", v_out.raw);
-            Ok(())
+        // Eq for Semver
+        Some(("uniq", args)) => {
+            let mut seen = HashSet::new();
+            let versions = written(args)?
+                .into_iter()
+                .filter(|(_, v)| seen.insert(v.clone()))
+                .collect();
+            Ok(list(versions))
+        }
+
+        // Ord for Semver
+        Some(("compare", args)) => {
+            let (left, right, ordering) = compare(args)?;
+            let plain = match ordering {
+                Ordering::Less => "-1",
+                Ordering::Equal => "0",
+                Ordering::Greater => "1",
+            };
+            Ok(Output::new(plain, comparison(left, right, ordering)))
         }
 
         // Range::validate
         Some(("validate-range", args)) => {
-            let range = get_arg::<Range>(args, "range")?;
-            println!("{} is valid", range.raw);
-            Ok(())
+            let range = Range::parse(&get_arg::<String>(args, "range")?)?;
+            Ok(Output::new(
+                format!("{} is valid", range.raw),
+                json!({ "range": output::range(&range) }),
+            ))
         }
 
         // Range::satisfies
         Some(("satisfies", args)) => {
-            let range = get_arg::<Range>(args, "range")?;
-            let semver = get_arg::<Semver>(args, "semver")?;
-            if range.satisfies(&semver) {
-                println!("{} satisifes {}", semver.raw, range.raw);
-                Ok(())
+            let range = get_arg::<String>(args, "range")?;
+            let semver = get_arg::<String>(args, "semver")?;
+            let options = satisfy_options(args)?;
+            let (satisfied, fields) = match get_arg::<String>(args, "dialect")?.as_str() {
+                "pep440" => {
+                    let set = SpecifierSet::parse(&range)?;
+                    let v = Version::parse(&semver)?;
+                    let fields = json!({
+                        "range": output::specifiers(&set),
+                        "version": output::pep440(&v),
+                    });
+                    (set.satisfies(&v), fields)
+                }
+                _ => {
+                    let r = Range::parse(&range)?;
+                    let v = Semver::parse(&semver)?;
+                    let fields = json!({
+                        "range": output::range(&r),
+                        "version": output::semver(&v),
+                    });
+                    (r.satisfies_with(&v, &options), fields)
+                }
+            };
+
+            if satisfied {
+                let plain = format!("{semver} satisifes {range}");
+                Ok(Output::check(true, plain, fields))
             } else {
-                bail!("{} doesn't satisify {}", semver.raw, range.raw);
+                let plain = format!("{semver} doesn't satisify {range}");
+                Ok(Output::check(false, plain, fields))
             }
         }
 
         // Range::max
         Some(("max", args)) => {
-            let range = get_arg::<Range>(args, "range")?;
-            let semvers = get_arg_vec::<Semver>(args, "semver")?;
-            match range.max(&semvers) {
-                Some(semver) => {
-                    println!("{}", semver.raw);
-                    Ok(())
+            let range = get_arg::<String>(args, "range")?;
+            let semvers = get_arg_vec::<String>(args, "semver")?;
+            let options = satisfy_options(args)?;
+            let (range, max) = match get_arg::<String>(args, "dialect")?.as_str() {
+                "pep440" => {
+                    let set = SpecifierSet::parse(&range)?;
+                    let versions = semvers
+                        .iter()
+                        .map(|v| Version::parse(v))
+                        .collect::<libsemverator::Result<Vec<_>>>()?;
+                    let max = set.max(&versions).map(|v| output::pep440(&v));
+                    (output::specifiers(&set), max)
+                }
+                _ => {
+                    let r = Range::parse(&range)?;
+                    let semvers = semvers
+                        .iter()
+                        .map(|v| Semver::parse(v))
+                        .collect::<libsemverator::Result<Vec<_>>>()?;
+                    let max = r.max_with(&semvers, &options).map(|v| output::semver(&v));
+                    (output::range(&r), max)
+                }
+            };
+            Ok(candidate(range, max))
+        }
+
+        // Range::min
+        Some(("min", args)) => {
+            let range = get_arg::<String>(args, "range")?;
+            let semvers = get_arg_vec::<String>(args, "semver")?;
+            let options = satisfy_options(args)?;
+            let (range, min) = match get_arg::<String>(args, "dialect")?.as_str() {
+                "pep440" => {
+                    let set = SpecifierSet::parse(&range)?;
+                    let versions = semvers
+                        .iter()
+                        .map(|v| Version::parse(v))
+                        .collect::<libsemverator::Result<Vec<_>>>()?;
+                    let min = set.min(&versions).map(|v| output::pep440(&v));
+                    (output::specifiers(&set), min)
                 }
-                None => bail!("no viable candidates"),
+                _ => {
+                    let r = Range::parse(&range)?;
+                    let semvers = semvers
+                        .iter()
+                        .map(|v| Semver::parse(v))
+                        .collect::<libsemverator::Result<Vec<_>>>()?;
+                    let min = r.min_with(&semvers, &options).map(|v| output::semver(&v));
+                    (output::range(&r), min)
+                }
+            };
+            Ok(candidate(range, min))
+        }
+
+        // Range::filter
+        Some(("filter", args)) => {
+            let range = get_arg::<String>(args, "range")?;
+            let semvers = get_arg_vec::<String>(args, "semver")?;
+            let options = satisfy_options(args)?;
+            let (range, matches) = match get_arg::<String>(args, "dialect")?.as_str() {
+                "pep440" => {
+                    let set = SpecifierSet::parse(&range)?;
+                    let versions = semvers
+                        .iter()
+                        .map(|v| Version::parse(v))
+                        .collect::<libsemverator::Result<Vec<_>>>()?;
+                    let matches = set.filter(&versions).into_iter().map(output::pep440);
+                    (output::specifiers(&set), matches.collect::<Vec<_>>())
+                }
+                _ => {
+                    let r = Range::parse(&range)?;
+                    let semvers = semvers
+                        .iter()
+                        .map(|v| Semver::parse(v))
+                        .collect::<libsemverator::Result<Vec<_>>>()?;
+                    let matches = r.filter_with(&semvers, &options).map(output::semver);
+                    (output::range(&r), matches.collect::<Vec<_>>())
+                }
+            };
+            let plain = matches.iter().map(raw).collect::<Vec<_>>().join("\n");
+            let fields = json!({ "range": range, "versions": matches });
+
+            if matches.is_empty() {
+                Ok(Output::check(false, "no viable candidates", fields))
+            } else {
+                Ok(Output::new(plain, fields))
             }
         }
 
         // Range::intersect
         Some(("intersect", args)) => {
-            let left = get_arg::<Range>(args, "left")?;
-            let right = get_arg::<Range>(args, "right")?;
+            let (left, right) = ranges(args)?;
+
+            Ok(range(left.intersect(&right)?))
+        }
+
+        // Range::union
+        Some(("union", args)) => {
+            let (left, right) = ranges(args)?;
+
+            Ok(range(left.union(&right)))
+        }
+
+        // Range::difference
+        Some(("difference", args)) => {
+            let (left, right) = ranges(args)?;
+
+            Ok(range(left.difference(&right)))
+        }
+
+        // Range::complement
+        Some(("complement", args)) => {
+            let input = Range::parse(&get_arg::<String>(args, "range")?)?;
+
+            Ok(range(input.complement()))
+        }
 
-            let intersection = left.intersect(&right)?;
-            println!("{}", intersection.raw);
-            Ok(())
+        // Range::is_subset_of
+        Some(("subset", args)) => {
+            let (left, right) = ranges(args)?;
+            let fields = json!({
+                "left": output::range(&left),
+                "right": output::range(&right),
+            });
+
+            if left.is_subset_of(&right) {
+                let plain = format!("{} is a subset of {}", left.raw, right.raw);
+                Ok(Output::check(true, plain, fields))
+            } else {
+                let plain = format!("{} is not a subset of {}", left.raw, right.raw);
+                Ok(Output::check(false, plain, fields))
+            }
+        }
+
+        // Range::is_disjoint
+        Some(("disjoint", args)) => {
+            let (left, right) = ranges(args)?;
+            let fields = json!({
+                "left": output::range(&left),
+                "right": output::range(&right),
+            });
+
+            if left.is_disjoint(&right) {
+                let plain = format!("{} and {} are disjoint", left.raw, right.raw);
+                Ok(Output::check(true, plain, fields))
+            } else {
+                let plain = format!("{} and {} overlap", left.raw, right.raw);
+                Ok(Output::check(false, plain, fields))
+            }
         }
 
-        Some((cmd, _)) => unimplemented!("{cmd} isn't implemented"),
+        Some((cmd, _)) => bail!("{cmd} isn't implemented"),
         None => bail!("no command supplied"),
     }
 }
+
+// The `--include-prerelease` policy, which only the semver dialect takes.
+#[cfg(not(tarpaulin_include))]
+fn satisfy_options(args: &ArgMatches) -> Result<SatisfyOptions> {
+    let Some(policy) = args.get_one::<String>("include-prerelease") else {
+        return Ok(SatisfyOptions::default());
+    };
+    if get_arg::<String>(args, "dialect")? != "semver" {
+        bail!("--include-prerelease only applies to the semver dialect");
+    }
+    Ok(SatisfyOptions {
+        include_prerelease: PrereleasePolicy::parse(policy)?,
+    })
+}
+
+// Parses the `left` and `right` versions under the chosen `--dialect` and
+// orders them, returning their JSON descriptions.
+#[cfg(not(tarpaulin_include))]
+fn compare(args: &ArgMatches) -> Result<(Value, Value, Ordering)> {
+    fn ordered<T: Ord>(
+        args: &ArgMatches,
+        parse: fn(&str) -> libsemverator::Result<T>,
+        describe: fn(&T) -> Value,
+    ) -> Result<(Value, Value, Ordering)> {
+        let left = parse(&get_arg::<String>(args, "left")?)?;
+        let right = parse(&get_arg::<String>(args, "right")?)?;
+        Ok((describe(&left), describe(&right), left.cmp(&right)))
+    }
+
+    match get_arg::<String>(args, "dialect")?.as_str() {
+        "pep440" => ordered(args, Version::parse, output::pep440),
+        "debian" => ordered(args, debian::Version::parse, output::debian),
+        "rpm" => ordered(args, rpm::Version::parse, output::rpm),
+        _ => ordered(args, Semver::parse, output::semver),
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+fn ranges(args: &ArgMatches) -> Result<(Range, Range)> {
+    let left = Range::parse(&get_arg::<String>(args, "left")?)?;
+    let right = Range::parse(&get_arg::<String>(args, "right")?)?;
+    Ok((left, right))
+}
+
+// The `semver` args, as written and parsed.
+#[cfg(not(tarpaulin_include))]
+fn written(args: &ArgMatches) -> Result<Vec<(String, Semver)>> {
+    get_arg_vec::<String>(args, "semver")?
+        .into_iter()
+        .map(|v| {
+            let semver = Semver::parse(&v)?;
+            Ok((v, semver))
+        })
+        .collect()
+}
+
+// A version's raw form, as written (normalised for SemVer).
+fn raw(version: &Value) -> &str {
+    version["raw"].as_str().unwrap_or_default()
+}
+
+fn comparison(left: Value, right: Value, ordering: Ordering) -> Value {
+    let ordering = match ordering {
+        Ordering::Less => "less",
+        Ordering::Equal => "equal",
+        Ordering::Greater => "greater",
+    };
+    json!({ "left": left, "right": right, "ordering": ordering })
+}
+
+fn version(v: Semver) -> Output {
+    Output::new(v.raw.clone(), json!({ "version": output::semver(&v) }))
+}
+
+fn range(r: Range) -> Output {
+    Output::new(r.raw.clone(), json!({ "range": output::range(&r) }))
+}
+
+// Versions printed as they were written, one per line.
+fn list(versions: Vec<(String, Semver)>) -> Output {
+    let plain = versions
+        .iter()
+        .map(|(v, _)| v.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let versions = versions
+        .iter()
+        .map(|(_, v)| output::semver(v))
+        .collect::<Vec<_>>();
+    Output::new(plain, json!({ "versions": versions }))
+}
+
+// The answer to `max` or `min`: a version, or a failed check if none fit.
+fn candidate(range: Value, version: Option<Value>) -> Output {
+    match version {
+        Some(v) => Output::new(raw(&v), json!({ "range": range, "version": v })),
+        None => Output::check(
+            false,
+            "no viable candidates",
+            json!({ "range": range, "version": null }),
+        ),
+    }
+}
@@ -0,0 +1,269 @@
+//! What a subcommand produced, and how to print it for `--format`.
+
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+use anyhow::{Error, Result};
+use libsemverator::{
+    range::{dialect::pep440::SpecifierSet, Range},
+    semver::{
+        dialect::{debian, pep440, rpm},
+        prerelease::Identifier,
+        Semver,
+    },
+    ErrorKind,
+};
+use serde_json::{json, Map, Value};
+
+/// The result of a subcommand: what to print, in either format, and whether
+/// its check held (a failed check exits non-zero in both).
+pub struct Output {
+    pub ok: bool,
+    pub plain: String,
+    pub json: Value,
+}
+
+impl Output {
+    /// A successful command, with `fields` for its JSON document.
+    pub fn new(plain: impl Into<String>, fields: Value) -> Self {
+        Self {
+            ok: true,
+            plain: plain.into(),
+            json: fields,
+        }
+    }
+
+    /// A command whose answer is yes or no. The JSON document gets a
+    /// `result` field.
+    pub fn check(ok: bool, plain: impl Into<String>, mut fields: Value) -> Self {
+        if let Value::Object(map) = &mut fields {
+            map.insert("result".to_string(), json!(ok));
+        }
+        Self {
+            ok,
+            plain: plain.into(),
+            json: fields,
+        }
+    }
+
+    /// The JSON document for `command`: its fields, plus `command` and
+    /// `ok: true`.
+    pub fn document(&self, command: &str) -> Value {
+        let mut rv = Map::new();
+        rv.insert("command".to_string(), json!(command));
+        rv.insert("ok".to_string(), json!(true));
+        if let Value::Object(fields) = &self.json {
+            rv.extend(fields.clone());
+        }
+        Value::Object(rv)
+    }
+}
+
+/// How the CLI exits: whether a command's check held, and if it couldn't
+/// answer, why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    /// The command succeeded, or its check held.
+    True = 0,
+    /// The check didn't hold, eg. versions weren't equal or none satisfied a
+    /// range.
+    False = 1,
+    /// A version, range or other input didn't parse.
+    Parse = 2,
+    /// The command line was wrong.
+    Usage = 3,
+    /// A version parsed but couldn't be bumped as asked, eg. a component
+    /// would overflow.
+    Bump = 4,
+}
+
+impl Status {
+    pub fn of(e: &Error) -> Self {
+        match e.downcast_ref::<libsemverator::Error>() {
+            Some(libsemverator::Error::Bump(_)) => Self::Bump,
+            Some(
+                libsemverator::Error::Semver(_)
+                | libsemverator::Error::Range(_)
+                | libsemverator::Error::Parse(_),
+            ) => Self::Parse,
+            None => Self::Usage,
+        }
+    }
+}
+
+/// Prints results per `--format` and `--quiet`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Printer {
+    pub json: bool,
+    pub quiet: bool,
+}
+
+impl Printer {
+    /// Prints what `command` produced, numbered with its `line` of standard
+    /// input if it has one, and returns how it should exit. Results go to
+    /// stdout, which `--quiet` silences; errors go to stderr, unless they're
+    /// JSON.
+    pub fn report(&self, command: &str, line: Option<usize>, result: Result<Output>) -> Status {
+        match result {
+            Ok(out) => {
+                if self.json {
+                    self.print(numbered(out.document(command), line));
+                } else {
+                    self.print(&out.plain);
+                }
+                if out.ok {
+                    Status::True
+                } else {
+                    Status::False
+                }
+            }
+            Err(e) => {
+                if self.json {
+                    self.print(numbered(error(Some(command), &e), line));
+                } else {
+                    let message = match e.downcast_ref::<libsemverator::Error>() {
+                        Some(e) => e.render(),
+                        None => e.to_string(),
+                    };
+                    // Nowhere left to report to if stderr's gone.
+                    let _ = match line {
+                        Some(n) => writeln!(io::stderr(), "line {n}: {message}"),
+                        None => writeln!(io::stderr(), "Error: {message}"),
+                    };
+                }
+                Status::of(&e)
+            }
+        }
+    }
+
+    /// Prints a command line that `clap` rejected, as [`Printer::report`]
+    /// prints errors, for `command` if it got that far.
+    pub fn usage(&self, command: Option<&str>, e: Error) -> Status {
+        if self.json {
+            self.print(error(command, &e));
+        } else {
+            let _ = writeln!(io::stderr(), "Error: {e}");
+        }
+        Status::of(&e)
+    }
+
+    // Unlike `println!`, doesn't panic when stdout is closed early, as by
+    // `semverator sort ... | head -1`.
+    fn print(&self, text: impl Display) {
+        if !self.quiet {
+            let _ = writeln!(io::stdout(), "{text}");
+        }
+    }
+}
+
+// Adds the line of standard input a document came from.
+fn numbered(mut document: Value, line: Option<usize>) -> Value {
+    if let (Value::Object(map), Some(n)) = (&mut document, line) {
+        map.insert("line".to_string(), n.into());
+    }
+    document
+}
+
+/// The JSON document for a command that failed with `e`, with `ok: false`.
+pub fn error(command: Option<&str>, e: &Error) -> Value {
+    let (kind, span) = match e.downcast_ref::<libsemverator::Error>() {
+        Some(libsemverator::Error::Semver(_)) => ("semver", None),
+        Some(libsemverator::Error::Range(_)) => ("range", None),
+        Some(libsemverator::Error::Bump(_)) => ("bump", None),
+        Some(libsemverator::Error::Parse(d)) => (kind(&d.kind), Some([d.span.start, d.span.end])),
+        None => ("usage", None),
+    };
+    json!({
+        "command": command,
+        "ok": false,
+        "error": {
+            "kind": kind,
+            "message": e.to_string(),
+            "span": span,
+        },
+    })
+}
+
+// A stable name for each kind of parse error.
+fn kind(kind: &ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::LeadingZero(_) => "leading_zero",
+        ErrorKind::EmptyIdentifier(_) => "empty_identifier",
+        ErrorKind::UnknownCharacter(_) => "unknown_character",
+        ErrorKind::Overflow(_) => "overflow",
+        ErrorKind::ComponentCount(_) => "component_count",
+        ErrorKind::Prefix => "prefix",
+        ErrorKind::BadOperator(_) => "bad_operator",
+        ErrorKind::InvertedBounds => "inverted_bounds",
+        ErrorKind::EmptyConstraint => "empty_constraint",
+    }
+}
+
+pub fn semver(v: &Semver) -> Value {
+    let prerelease = v
+        .prerelease
+        .iter()
+        .map(|i| match i {
+            Identifier::Numeric(n) => json!(n),
+            Identifier::AlphaNumeric(s) => json!(s),
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "raw": v,
+        "components": v.components,
+        "major": v.major,
+        "minor": v.minor,
+        "patch": v.patch,
+        "prerelease": prerelease,
+        "build": v.build,
+    })
+}
+
+pub fn pep440(v: &pep440::Version) -> Value {
+    json!({
+        "raw": v.to_string(),
+        "epoch": v.epoch,
+        "release": v.release,
+        "pre": v.pre.as_ref().map(|(phase, n)| json!([phase.to_string(), n])),
+        "post": v.post,
+        "dev": v.dev,
+        "local": v.local,
+    })
+}
+
+pub fn debian(v: &debian::Version) -> Value {
+    json!({
+        "raw": v.to_string(),
+        "epoch": v.epoch,
+        "upstream": v.upstream,
+        "revision": v.revision,
+    })
+}
+
+pub fn rpm(v: &rpm::Version) -> Value {
+    json!({
+        "raw": v.to_string(),
+        "epoch": v.epoch,
+        "version": v.version,
+        "release": v.release,
+    })
+}
+
+pub fn range(r: &Range) -> Value {
+    json!({
+        "raw": r.raw,
+        "display": r,
+        "constraints": r.set.iter().map(|c| c.raw()).collect::<Vec<_>>(),
+    })
+}
+
+/// A PEP 440 specifier set, in the same shape as [`range`].
+pub fn specifiers(s: &SpecifierSet) -> Value {
+    json!({
+        "raw": s.raw,
+        "display": s.to_string(),
+        "constraints": s.specifiers.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+    })
+}
@@ -0,0 +1,241 @@
+//! Evaluates the REPL's expressions with the library's own types and
+//! operations.
+
+use std::{cmp::Ordering, collections::HashMap, collections::HashSet, fmt};
+
+use anyhow::{bail, Result};
+use libsemverator::{
+    range::Range,
+    semver::{bump::SemverComponent, Semver},
+};
+
+use super::parse::{is_name, Expr, Op};
+
+/// The functions an expression can call, for `:help`.
+pub const FUNCTIONS: &str = "\
+  max(range, [versions])     the greatest version that satisfies range, or none
+  min(range, [versions])     the least version that satisfies range, or none
+  filter(range, [versions])  the versions that satisfy range
+  sort([versions])           versions, lowest first
+  uniq([versions])           versions, without any equal to one before them
+  bump(version, bump)        version bumped, eg. bump(1.2.3, minor)
+  bump(version, bump, id)    version bumped to a prerelease, eg. bump(1.2.3, premajor, rc)
+  complement(range)          the versions outside range
+  subset(left, right)        whether every version in left is in right
+  disjoint(left, right)      whether no version is in both
+  compare(left, right)       -1, 0 or 1 as left is less than, equal to or greater than right";
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Version(Semver),
+    Range(Range),
+    Bool(bool),
+    Number(i64),
+    List(Vec<Value>),
+    /// What `max` and `min` give when no version fits.
+    Nothing,
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Version(_) => "version",
+            Self::Range(_) => "range",
+            Self::Bool(_) => "boolean",
+            Self::Number(_) => "number",
+            Self::List(_) => "list",
+            Self::Nothing => "nothing",
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Version(v) => write!(f, "{}", v.raw),
+            Self::Range(r) => write!(f, "{r}"),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Number(n) => write!(f, "{n}"),
+            Self::List(items) => {
+                let items = items.iter().map(Value::to_string).collect::<Vec<_>>();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Self::Nothing => write!(f, "none"),
+        }
+    }
+}
+
+pub type Vars = HashMap<String, Value>;
+
+pub fn eval(expr: &Expr, vars: &Vars) -> Result<Value> {
+    match expr {
+        Expr::Word(word) => match vars.get(word) {
+            Some(value) => Ok(value.clone()),
+            None => literal(word),
+        },
+        Expr::Text(text) => literal(text),
+        Expr::List(items) => Ok(Value::List(
+            items.iter().map(|e| eval(e, vars)).collect::<Result<_>>()?,
+        )),
+        Expr::Call(name, args) => call(name, args, vars),
+        Expr::Binary(op, left, right) => binary(*op, eval(left, vars)?, eval(right, vars)?),
+    }
+}
+
+// A version if `text` is one, or else a range.
+fn literal(text: &str) -> Result<Value> {
+    if let Ok(v) = Semver::parse(text) {
+        return Ok(Value::Version(v));
+    }
+    match Range::parse(text) {
+        Ok(r) => Ok(Value::Range(r)),
+        Err(_) if is_name(text) => bail!("'{text}' isn't a variable, version or range"),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn binary(op: Op, left: Value, right: Value) -> Result<Value> {
+    Ok(match op {
+        Op::And => Value::Range(range(left)?.intersect(&range(right)?)?),
+        Op::Or => Value::Range(range(left)?.union(&range(right)?)),
+        Op::Minus => Value::Range(range(left)?.difference(&range(right)?)),
+        Op::In => {
+            let v = version(left)?;
+            match right {
+                Value::List(items) => Value::Bool(
+                    items
+                        .into_iter()
+                        .map(version)
+                        .collect::<Result<Vec<_>>>()?
+                        .contains(&v),
+                ),
+                right => Value::Bool(range(right)?.satisfies(&v)),
+            }
+        }
+        Op::Eq => Value::Bool(equal(left, right)?),
+        Op::Ne => Value::Bool(!equal(left, right)?),
+        Op::Lt => Value::Bool(version(left)? < version(right)?),
+        Op::Le => Value::Bool(version(left)? <= version(right)?),
+        Op::Gt => Value::Bool(version(left)? > version(right)?),
+        Op::Ge => Value::Bool(version(left)? >= version(right)?),
+    })
+}
+
+// Versions are equal by precedence, and ranges if they hold the same
+// versions.
+fn equal(left: Value, right: Value) -> Result<bool> {
+    Ok(match (left, right) {
+        (Value::Version(a), Value::Version(b)) => a == b,
+        (Value::Bool(a), Value::Bool(b)) => a == b,
+        (Value::Number(a), Value::Number(b)) => a == b,
+        (Value::Nothing, Value::Nothing) => true,
+        (left, right) => range(left)?.equivalent(&range(right)?),
+    })
+}
+
+fn call(name: &str, args: &[Expr], vars: &Vars) -> Result<Value> {
+    let arg = |i: usize| eval(&args[i], vars);
+    let arity = |n: &[usize]| {
+        if !n.contains(&args.len()) {
+            let n = n.iter().map(usize::to_string).collect::<Vec<_>>();
+            bail!("{name}() takes {} arguments", n.join(" or "));
+        }
+        Ok(())
+    };
+
+    match name {
+        "max" | "min" | "filter" => {
+            arity(&[2])?;
+            let r = range(arg(0)?)?;
+            let versions = versions(arg(1)?)?;
+            Ok(match name {
+                "max" => r.max(&versions).map_or(Value::Nothing, Value::Version),
+                "min" => r.min(&versions).map_or(Value::Nothing, Value::Version),
+                _ => Value::List(r.filter(&versions).cloned().map(Value::Version).collect()),
+            })
+        }
+        "sort" => {
+            arity(&[1])?;
+            let mut versions = versions(arg(0)?)?;
+            versions.sort();
+            Ok(Value::List(
+                versions.into_iter().map(Value::Version).collect(),
+            ))
+        }
+        "uniq" => {
+            arity(&[1])?;
+            let mut seen = HashSet::new();
+            Ok(Value::List(
+                versions(arg(0)?)?
+                    .into_iter()
+                    .filter(|v| seen.insert(v.clone()))
+                    .map(Value::Version)
+                    .collect(),
+            ))
+        }
+        "bump" => {
+            arity(&[2, 3])?;
+            let v = version(arg(0)?)?;
+            let which = SemverComponent::parse(word(&args[1])?)?;
+            let preid = args.get(2).map(word).transpose()?;
+            Ok(Value::Version(v.bump_with(&which, preid)?))
+        }
+        "complement" => {
+            arity(&[1])?;
+            Ok(Value::Range(range(arg(0)?)?.complement()))
+        }
+        "subset" => {
+            arity(&[2])?;
+            Ok(Value::Bool(range(arg(0)?)?.is_subset_of(&range(arg(1)?)?)))
+        }
+        "disjoint" => {
+            arity(&[2])?;
+            Ok(Value::Bool(range(arg(0)?)?.is_disjoint(&range(arg(1)?)?)))
+        }
+        "compare" => {
+            arity(&[2])?;
+            Ok(Value::Number(
+                match version(arg(0)?)?.cmp(&version(arg(1)?)?) {
+                    Ordering::Less => -1,
+                    Ordering::Equal => 0,
+                    Ordering::Greater => 1,
+                },
+            ))
+        }
+        _ => bail!("unknown function '{name}'; try :help"),
+    }
+}
+
+fn version(value: Value) -> Result<Semver> {
+    match value {
+        Value::Version(v) => Ok(v),
+        other => bail!("expected a version, found {other} (a {})", other.kind()),
+    }
+}
+
+// A version counts as the range of just itself.
+fn range(value: Value) -> Result<Range> {
+    match value {
+        Value::Range(r) => Ok(r),
+        Value::Version(v) => Ok(Range::from_semver(&v)?),
+        other => bail!("expected a range, found {other} (a {})", other.kind()),
+    }
+}
+
+fn versions(value: Value) -> Result<Vec<Semver>> {
+    match value {
+        Value::List(items) => items.into_iter().map(version).collect(),
+        other => bail!(
+            "expected a list of versions, found {other} (a {})",
+            other.kind()
+        ),
+    }
+}
+
+// A bare word, like the `minor` in `bump(1.2.3, minor)`.
+fn word(expr: &Expr) -> Result<&str> {
+    match expr {
+        Expr::Word(w) | Expr::Text(w) => Ok(w),
+        _ => bail!("expected a word, like minor or rc"),
+    }
+}
@@ -0,0 +1,166 @@
+//! `semverator repl`: an interactive session for exploring versions and
+//! ranges, with variables and history, without a process per question.
+
+pub mod eval;
+pub mod parse;
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{bail, Result};
+
+use crate::output::Status;
+use eval::{eval, Vars};
+use parse::Statement;
+
+const HELP: &str = "\
+Expressions:
+  1.2.3, ^1.2, >=1<2          versions and ranges, written bare; quote ones with commas
+  left & right                versions in both ranges
+  left | right                versions in either range
+  left - right                versions in left but not right
+  version in range            whether version satisfies range (or is in a list)
+  left == right, <, <=, >, >= compare versions, or ranges by the versions they hold
+  [1.0, 1.5, 2.0]             a list of versions
+  name = expression           sets a variable; _ is the last result
+
+Functions:
+";
+
+const COMMANDS: &str = "\
+Commands:
+  :help     this help
+  :vars     the variables set so far
+  :history  the lines entered so far; !n runs line n again, and !! the last
+  :quit     leaves the repl, as does end of input";
+
+/// What to show for a line of input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    Show(String),
+    Nothing,
+    Quit,
+}
+
+#[derive(Debug, Default)]
+pub struct Session {
+    vars: Vars,
+    history: Vec<String>,
+}
+
+impl Session {
+    /// Runs a line of input: a statement, a `:command`, or `!n` to run line
+    /// `n` of the history again.
+    pub fn line(&mut self, input: &str) -> Result<Reply> {
+        let input = self.recall(input.trim())?;
+        if input.is_empty() {
+            return Ok(Reply::Nothing);
+        }
+        self.history.push(input.clone());
+
+        match input.as_str() {
+            ":help" | ":h" => Ok(Reply::Show(format!(
+                "{HELP}{}\n\n{COMMANDS}",
+                eval::FUNCTIONS
+            ))),
+            ":vars" => {
+                let mut vars = self
+                    .vars
+                    .iter()
+                    .map(|(name, value)| format!("{name} = {value}"))
+                    .collect::<Vec<_>>();
+                vars.sort();
+                if vars.is_empty() {
+                    return Ok(Reply::Show("no variables set".to_string()));
+                }
+                Ok(Reply::Show(vars.join("\n")))
+            }
+            ":history" => Ok(Reply::Show(
+                self.history
+                    .iter()
+                    .enumerate()
+                    .map(|(i, line)| format!("{:>4}  {line}", i + 1))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )),
+            ":quit" | ":q" | ":exit" => Ok(Reply::Quit),
+            command if command.starts_with(':') => {
+                bail!("unknown command '{command}'; try :help")
+            }
+            _ => match parse::parse(&input)? {
+                Statement::Let(name, expr) => {
+                    let value = eval(&expr, &self.vars)?;
+                    let reply = format!("{name} = {value}");
+                    self.vars.insert(name, value);
+                    Ok(Reply::Show(reply))
+                }
+                Statement::Expr(expr) => {
+                    let value = eval(&expr, &self.vars)?;
+                    let reply = value.to_string();
+                    self.vars.insert("_".to_string(), value);
+                    Ok(Reply::Show(reply))
+                }
+            },
+        }
+    }
+
+    // Expands `!!` and `!n` from the history. `!=1.2` is a range, so it's
+    // left alone.
+    fn recall(&self, input: &str) -> Result<String> {
+        let n = match input.strip_prefix('!') {
+            Some("!") => self.history.len(),
+            Some(n) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => {
+                n.parse().unwrap_or(0)
+            }
+            _ => return Ok(input.to_string()),
+        };
+        match n.checked_sub(1).and_then(|i| self.history.get(i)) {
+            Some(line) => Ok(line.clone()),
+            None => bail!("no line {input} in the history"),
+        }
+    }
+}
+
+/// Reads lines from standard input until it ends or `:quit`, printing each
+/// result. Returns the worst status of any line, as `--stdin` does.
+#[cfg(not(tarpaulin_include))]
+pub fn run() -> Status {
+    let mut session = Session::default();
+    let interactive = io::stdin().is_terminal();
+    let mut status = Status::True;
+    let mut stdin = io::stdin().lock();
+
+    if interactive {
+        let version = env!("CARGO_PKG_VERSION");
+        let _ = writeln!(io::stdout(), "semverator {version} repl; :help for help");
+    }
+    loop {
+        if interactive {
+            let _ = write!(io::stdout(), "> ");
+            let _ = io::stdout().flush();
+        }
+        let mut line = String::new();
+        match stdin.read_line(&mut line) {
+            Ok(0) => return status,
+            Ok(_) => (),
+            Err(e) => {
+                let _ = writeln!(io::stderr(), "error: {e}");
+                return Status::Usage;
+            }
+        }
+        match session.line(&line) {
+            Ok(Reply::Show(text)) => {
+                let _ = writeln!(io::stdout(), "{text}");
+            }
+            Ok(Reply::Nothing) => (),
+            Ok(Reply::Quit) => return status,
+            Err(e) => {
+                status = status.max(Status::of(&e));
+                let message = match e.downcast_ref::<libsemverator::Error>() {
+                    Some(e) => e.render(),
+                    None => e.to_string(),
+                };
+                let _ = writeln!(io::stderr(), "error: {message}");
+            }
+        }
+    }
+}
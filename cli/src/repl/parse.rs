@@ -0,0 +1,295 @@
+//! The REPL's expression language, eg. `^1.2 & ~1.4`, `1.2.3 in >=1<2` or
+//! `max(^1, [1.0, 1.5, 2.0])`.
+//!
+//! Versions and ranges are written bare, so anything that isn't punctuation
+//! or an operator standing on its own is a word: a variable, a function's
+//! name, or a version or range. Commas inside calls and lists separate
+//! arguments, so ranges with commas in them there need quotes, eg.
+//! `max("^1,!=1.5", [1.0, 1.5])`.
+
+use anyhow::{bail, Result};
+
+/// How deeply brackets can nest, so that a line of `(`s is an error rather
+/// than a stack overflow.
+const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    /// One of `(`, `)`, `[`, `]`, `,` or `&`.
+    Punct(char),
+    /// A run of anything else, or a quoted string.
+    Word { text: String, quoted: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// `&`: versions in both ranges.
+    And,
+    /// `|` or `||`: versions in either range.
+    Or,
+    /// `-`: versions in the first range but not the second.
+    Minus,
+    /// `in`: whether a version satisfies a range, or is in a list.
+    In,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// A variable, or a version or range written out.
+    Word(String),
+    /// A quoted version or range, never a variable.
+    Text(String),
+    List(Vec<Expr>),
+    Call(String, Vec<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    /// `name = expr`
+    Let(String, Expr),
+    Expr(Expr),
+}
+
+pub fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut rv = vec![];
+    let mut depth = 0usize;
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => text.push(c),
+                        None => bail!("unterminated string"),
+                    }
+                }
+                rv.push(Token::Word { text, quoted: true });
+            }
+            '(' | ')' | '[' | ']' | '&' | ',' if c != ',' || depth > 0 => {
+                chars.next();
+                match c {
+                    '(' | '[' => depth += 1,
+                    ')' | ']' => depth = depth.saturating_sub(1),
+                    _ => (),
+                }
+                rv.push(Token::Punct(c));
+            }
+            _ => {
+                let mut text = String::new();
+                while let Some(&c) = chars.peek() {
+                    let separates = c.is_whitespace()
+                        || matches!(c, '(' | ')' | '[' | ']' | '&' | '"')
+                        || (c == ',' && depth > 0);
+                    if separates {
+                        break;
+                    }
+                    text.push(c);
+                    chars.next();
+                }
+                rv.push(Token::Word {
+                    text,
+                    quoted: false,
+                });
+            }
+        }
+    }
+    Ok(rv)
+}
+
+pub fn parse(input: &str) -> Result<Statement> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens,
+        at: 0,
+        depth: 0,
+    };
+
+    let statement = match (parser.tokens.first(), parser.tokens.get(1)) {
+        (
+            Some(Token::Word {
+                text: name,
+                quoted: false,
+            }),
+            Some(Token::Word {
+                text: eq,
+                quoted: false,
+            }),
+        ) if eq == "=" => {
+            if !is_name(name) {
+                bail!("can't assign to '{name}'");
+            }
+            let name = name.clone();
+            parser.at = 2;
+            Statement::Let(name, parser.expr()?)
+        }
+        _ => Statement::Expr(parser.expr()?),
+    };
+    if let Some(token) = parser.tokens.get(parser.at) {
+        bail!("unexpected {}", describe(token));
+    }
+    Ok(statement)
+}
+
+/// Whether `word` could name a variable or function, eg. `x` or `my_range`.
+pub fn is_name(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    at: usize,
+    // How many of `primary` are running, one per bracket or call.
+    depth: usize,
+}
+
+impl Parser {
+    // Comparisons and `in`, which don't chain.
+    fn expr(&mut self) -> Result<Expr> {
+        let left = self.union()?;
+        let op = match self.operator() {
+            Some("in") => Op::In,
+            Some("==") => Op::Eq,
+            Some("!=") => Op::Ne,
+            Some("<") => Op::Lt,
+            Some("<=") => Op::Le,
+            Some(">") => Op::Gt,
+            Some(">=") => Op::Ge,
+            _ => return Ok(left),
+        };
+        self.at += 1;
+        Ok(Expr::Binary(op, Box::new(left), Box::new(self.union()?)))
+    }
+
+    // `|` and `-`, which bind looser than `&`.
+    fn union(&mut self) -> Result<Expr> {
+        let mut left = self.intersection()?;
+        loop {
+            let op = match self.operator() {
+                Some("|" | "||") => Op::Or,
+                Some("-") => Op::Minus,
+                _ => return Ok(left),
+            };
+            self.at += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.intersection()?));
+        }
+    }
+
+    fn intersection(&mut self) -> Result<Expr> {
+        let mut left = self.primary()?;
+        while self.tokens.get(self.at) == Some(&Token::Punct('&')) {
+            self.at += 1;
+            left = Expr::Binary(Op::And, Box::new(left), Box::new(self.primary()?));
+        }
+        Ok(left)
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        if self.depth == MAX_DEPTH {
+            bail!("brackets nested more than {MAX_DEPTH} deep");
+        }
+        self.depth += 1;
+        let rv = self.term();
+        self.depth -= 1;
+        rv
+    }
+
+    fn term(&mut self) -> Result<Expr> {
+        let Some(token) = self.tokens.get(self.at).cloned() else {
+            bail!("unexpected end of input");
+        };
+        self.at += 1;
+        match token {
+            Token::Punct('(') => {
+                let rv = self.expr()?;
+                self.expect(')')?;
+                Ok(rv)
+            }
+            Token::Punct('[') => Ok(Expr::List(self.items(']')?)),
+            Token::Word {
+                text,
+                quoted: false,
+            } if is_name(&text) && self.tokens.get(self.at) == Some(&Token::Punct('(')) => {
+                self.at += 1;
+                Ok(Expr::Call(text, self.items(')')?))
+            }
+            Token::Word { text, quoted: true } => Ok(Expr::Text(text)),
+            Token::Word { text, .. } if !is_operator(&text) => Ok(Expr::Word(text)),
+            token => bail!("unexpected {}", describe(&token)),
+        }
+    }
+
+    // Comma-separated expressions, up to and including `close`.
+    fn items(&mut self, close: char) -> Result<Vec<Expr>> {
+        let mut rv = vec![];
+        if self.tokens.get(self.at) == Some(&Token::Punct(close)) {
+            self.at += 1;
+            return Ok(rv);
+        }
+        loop {
+            rv.push(self.expr()?);
+            match self.tokens.get(self.at) {
+                Some(Token::Punct(',')) => self.at += 1,
+                Some(Token::Punct(c)) if *c == close => {
+                    self.at += 1;
+                    return Ok(rv);
+                }
+                Some(token) => bail!("expected ',' or '{close}', found {}", describe(token)),
+                None => bail!("expected '{close}'"),
+            }
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        match self.tokens.get(self.at) {
+            Some(Token::Punct(p)) if *p == c => {
+                self.at += 1;
+                Ok(())
+            }
+            Some(token) => bail!("expected '{c}', found {}", describe(token)),
+            None => bail!("expected '{c}'"),
+        }
+    }
+
+    // The next token, if it's an unquoted word that could be an operator.
+    fn operator(&self) -> Option<&str> {
+        match self.tokens.get(self.at) {
+            Some(Token::Word {
+                text,
+                quoted: false,
+            }) if is_operator(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+fn is_operator(word: &str) -> bool {
+    matches!(
+        word,
+        "in" | "==" | "!=" | "<" | "<=" | ">" | ">=" | "|" | "||" | "-" | "="
+    )
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Punct(c) => format!("'{c}'"),
+        Token::Word { text, .. } => format!("'{text}'"),
+    }
+}
@@ -4,3 +4,20 @@ use crate::args;
 fn test_command_setup() {
     args::setup().debug_assert();
 }
+
+#[test]
+fn test_wants_json() {
+    let argv = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+
+    assert!(args::wants_json(&argv("semverator --format json eq 1.2")));
+    assert!(args::wants_json(&argv("semverator eq 1.2 --format=json")));
+    assert!(!args::wants_json(&argv("semverator eq 1.2")));
+    assert!(!args::wants_json(&argv("semverator eq -- --format json")));
+
+    assert_eq!(
+        args::command_name(&argv("semverator --format json eq 1.2")),
+        Some("eq".to_string())
+    );
+    assert_eq!(args::command_name(&argv("semverator --format json")), None);
+    assert_eq!(args::command_name(&argv("semverator nope eq")), None);
+}
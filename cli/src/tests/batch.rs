@@ -0,0 +1,45 @@
+use crate::{args, batch};
+use anyhow::Result;
+
+fn argv(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn test_without_stdin() {
+    assert_eq!(
+        batch::without_stdin(&argv(&["semverator", "validate", "--stdin"])),
+        argv(&["semverator", "validate"])
+    );
+}
+
+#[test]
+fn test_with_fields() -> Result<()> {
+    let base = argv(&["semverator", "satisfies", "^1", "--dialect", "semver"]);
+    let line = batch::with_fields(&base, "-1".split_whitespace());
+
+    assert_eq!(
+        line,
+        argv(&[
+            "semverator",
+            "satisfies",
+            "^1",
+            "--dialect",
+            "semver",
+            "--",
+            "-1"
+        ])
+    );
+    let matches = args::setup().try_get_matches_from(&line)?;
+    let matches = matches.subcommand_matches("satisfies").unwrap();
+    assert_eq!(args::get_arg::<String>(matches, "range")?, "^1");
+    assert_eq!(args::get_arg::<String>(matches, "semver")?, "-1");
+
+    let base = argv(&["semverator", "eq", "--"]);
+    assert_eq!(
+        batch::with_fields(&base, "1.2 1.3".split_whitespace()),
+        argv(&["semverator", "eq", "--", "1.2", "1.3"])
+    );
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use crate::{
+    args, handle_command,
+    output::{Output, Status},
+};
+use anyhow::Result;
+
+fn run(argv: &[&str]) -> Result<Output> {
+    let matches = args::setup().try_get_matches_from(argv)?;
+    handle_command(matches.subcommand())
+}
+
+#[test]
+fn test_sort() -> Result<()> {
+    let versions = ["sort", "1.10", "v1.2", "2024.1", "1.2.0-rc.1", "1.2a"];
+
+    let out = run(&[&["semverator"], &versions[..]].concat())?;
+    assert_eq!(out.plain, "2024.1\n1.2.0-rc.1\nv1.2\n1.2a\n1.10");
+
+    let out = run(&[&["semverator"], &versions[..], &["--reverse"]].concat())?;
+    assert_eq!(out.plain, "1.10\n1.2a\nv1.2\n1.2.0-rc.1\n2024.1");
+    assert_eq!(out.json["versions"][0]["raw"], "1.10.0");
+
+    assert!(run(&["semverator", "sort", "1.2", "bad"]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_uniq() -> Result<()> {
+    let out = run(&[
+        "semverator",
+        "uniq",
+        "1.2",
+        "1.3",
+        "v1.2.0",
+        "1.2.0+b",
+        "1.2",
+    ])?;
+    assert_eq!(out.plain, "1.2\n1.3");
+
+    Ok(())
+}
+
+#[test]
+fn test_compare() -> Result<()> {
+    assert_eq!(run(&["semverator", "compare", "1.2", "1.10"])?.plain, "-1");
+    assert_eq!(run(&["semverator", "compare", "1.2", "1.2.0"])?.plain, "0");
+    assert_eq!(run(&["semverator", "compare", "1.2a", "1.2"])?.plain, "1");
+    assert_eq!(
+        run(&[
+            "semverator",
+            "compare",
+            "1.0",
+            "1.0.post1",
+            "--dialect",
+            "pep440"
+        ])?
+        .plain,
+        "-1"
+    );
+    assert!(run(&["semverator", "compare", "1.2"]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_overflow() {
+    let cases: [&[&str]; 4] = [
+        &["satisfies", "^18446744073709551615", "1.0.0"],
+        &["validate-range", "@18446744073709551615"],
+        &["validate-range", "~1.18446744073709551615"],
+        &["max", "^0.18446744073709551615", "1.0.0"],
+    ];
+
+    for case in cases {
+        let e = run(&[&["semverator"], case].concat()).err();
+        assert_eq!(e.as_ref().map(Status::of), Some(Status::Parse), "{case:?}");
+    }
+
+    // Components that would overflow, and indices far past the last one.
+    let bumps: [&[&str]; 5] = [
+        &["bump", "1.2.18446744073709551615", "patch"],
+        &["bump", "1.2.3-rc.18446744073709551615", "prerelease"],
+        &["bump", "1.2.3", "18446744073709551615"],
+        &["bump", "1.2.3", "10000000000"],
+        &["bump", "1.2.3", "99999999999999"],
+    ];
+    for case in bumps {
+        let e = run(&[&["semverator"], case].concat()).err();
+        assert_eq!(e.as_ref().map(Status::of), Some(Status::Bump), "{case:?}");
+    }
+}
+
+#[test]
+fn test_range_json() -> Result<()> {
+    // Commands that make a range and those that take one describe it alike.
+    let made = run(&["semverator", "validate-range", ">=1.2.0<1.3.0"])?;
+    let taken = run(&["semverator", "satisfies", ">=1.2.0<1.3.0", "1.2.5"])?;
+    assert_eq!(made.json["range"], taken.json["range"]);
+    assert_eq!(taken.json["range"]["display"], "~1.2");
+
+    let out = run(&["semverator", "subset", "~1.2", "^1"])?;
+    assert_eq!(out.json["left"]["raw"], "~1.2");
+    assert_eq!(
+        out.json["right"]["constraints"],
+        serde_json::json!([">=1.0.0<2.0.0"])
+    );
+
+    let out = run(&[
+        "semverator",
+        "max",
+        ">=1.0,!=1.5.*",
+        "1.5.2",
+        "1.4",
+        "--dialect",
+        "pep440",
+    ])?;
+    assert_eq!(out.json["range"]["raw"], ">=1.0,!=1.5.*");
+    assert_eq!(
+        out.json["range"]["constraints"],
+        serde_json::json!([">=1.0", "!=1.5.*"])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_bump_status() {
+    let cases: [(&[&str], Status); 7] = [
+        (&["bump", "1.1.1z", "letter"], Status::Bump),
+        (&["bump", "1.2.3", "6"], Status::Bump),
+        (&["bump", "1.2.3", "10000000000"], Status::Bump),
+        (&["bump", "18446744073709551615.0.0", "major"], Status::Bump),
+        (
+            &[
+                "bump",
+                "2026.10",
+                "--calver",
+                "YYYY.0M",
+                "--date",
+                "2026-10-18",
+            ],
+            Status::Bump,
+        ),
+        (&["bump", "1.2.x", "major"], Status::Parse),
+        (&["bump", "1.2.3", "sideways"], Status::Usage),
+    ];
+
+    for (case, status) in cases {
+        let e = run(&[&["semverator"], case].concat()).err();
+        assert_eq!(e.as_ref().map(Status::of), Some(status), "{case:?}");
+    }
+}
+
+#[test]
+fn test_min_filter() -> Result<()> {
+    let versions = ["1.2.3-rc", "1.3.0", "1.4.0-rc.1"];
+
+    let out = run(&[&["semverator", "min", "^1.2"], &versions[..]].concat())?;
+    assert_eq!(out.plain, "1.2.3-rc");
+    let out = run(&[
+        &["semverator", "min", "^1.2"],
+        &versions[..],
+        &["--include-prerelease", "never"],
+    ]
+    .concat())?;
+    assert_eq!(out.plain, "1.3.0");
+
+    let out = run(&[
+        &["semverator", "filter", "^1.2"],
+        &versions[..],
+        &["--include-prerelease", "same-tuple"],
+    ]
+    .concat())?;
+    assert_eq!(out.plain, "1.3.0");
+
+    let pep440 = ["1.0", "1.5", "2.0rc1"];
+    let out = run(&[
+        &["semverator", "min", ">1.0"],
+        &pep440[..],
+        &["--dialect", "pep440"],
+    ]
+    .concat())?;
+    assert_eq!(out.plain, "1.5");
+    let out = run(&[
+        &["semverator", "filter", ">=1.0,!=1.5"],
+        &pep440[..],
+        &["--dialect", "pep440"],
+    ]
+    .concat())?;
+    assert_eq!(out.plain, "1.0");
+    assert_eq!(out.json["range"]["raw"], ">=1.0,!=1.5");
+
+    assert!(run(&[
+        "semverator",
+        "filter",
+        "^1",
+        "1.0.0",
+        "--dialect",
+        "pep440",
+        "--include-prerelease",
+        "never"
+    ])
+    .is_err());
+
+    Ok(())
+}
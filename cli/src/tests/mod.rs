@@ -1 +1,5 @@
 mod args;
+mod batch;
+mod commands;
+mod output;
+mod repl;
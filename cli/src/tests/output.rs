@@ -0,0 +1,108 @@
+use crate::output::{self, Output, Printer, Status};
+use anyhow::{anyhow, Result};
+use libsemverator::{
+    range::Range,
+    semver::{bump::SemverComponent, Semver},
+};
+use serde_json::json;
+
+#[test]
+fn test_document() {
+    let a = Output::new("1.2.3", json!({ "version": "1.2.3" }));
+    let b = Output::check(false, "versions are not equal", json!({}));
+
+    assert!(a.ok);
+    assert_eq!(
+        a.document("bump"),
+        json!({ "command": "bump", "ok": true, "version": "1.2.3" })
+    );
+    assert!(!b.ok);
+    assert_eq!(b.plain, "versions are not equal");
+    assert_eq!(
+        b.document("eq"),
+        json!({ "command": "eq", "ok": true, "result": false })
+    );
+}
+
+#[test]
+fn test_semver() -> Result<()> {
+    let v = output::semver(&Semver::parse("v1.2.3-rc.1+b40")?);
+
+    assert_eq!(v["raw"], "1.2.3-rc.1+b40");
+    assert_eq!(v["components"], json!([1, 2, 3]));
+    assert_eq!(v["major"], 1);
+    assert_eq!(v["prerelease"], json!(["rc", 1]));
+    assert_eq!(v["build"], json!(["b40"]));
+
+    Ok(())
+}
+
+#[test]
+fn test_range() -> Result<()> {
+    let r = output::range(&Range::parse(">=1.2.0<1.3.0,=2")?);
+
+    assert_eq!(r["raw"], ">=1.2.0<1.3.0,=2");
+    assert_eq!(r["display"], "~1.2,=2");
+    assert_eq!(r["constraints"], json!([">=1.2.0<1.3.0", "=2.0.0"]));
+
+    Ok(())
+}
+
+#[test]
+fn test_error() {
+    let e = anyhow::Error::from(Semver::parse("1.2.x").unwrap_err());
+    assert_eq!(
+        output::error(Some("validate"), &e),
+        json!({
+            "command": "validate",
+            "ok": false,
+            "error": {
+                "kind": "unknown_character",
+                "message": "unexpected character 'x' at byte 4 of '1.2.x'",
+                "span": [4, 5],
+            },
+        })
+    );
+
+    let e = anyhow!("no command supplied");
+    assert_eq!(output::error(None, &e)["error"]["kind"], "usage");
+    assert_eq!(output::error(None, &e)["command"], json!(null));
+}
+
+#[test]
+fn test_status() {
+    let printer = Printer {
+        json: false,
+        quiet: true,
+    };
+    let parse = anyhow::Error::from(Semver::parse("1.2.x").unwrap_err());
+
+    assert_eq!(Status::of(&parse), Status::Parse);
+    assert_eq!(Status::of(&anyhow!("no command supplied")), Status::Usage);
+
+    let bump = anyhow::Error::from(
+        Semver::parse("1.1.1z")
+            .unwrap()
+            .bump(&SemverComponent::Letter)
+            .unwrap_err(),
+    );
+    assert_eq!(Status::of(&bump), Status::Bump);
+    assert_eq!(output::error(Some("bump"), &bump)["error"]["kind"], "bump");
+
+    let ok = Output::check(true, "versions are equal", json!({}));
+    let not = Output::check(false, "versions are not equal", json!({}));
+    assert_eq!(printer.report("eq", None, Ok(ok)), Status::True);
+    assert_eq!(printer.report("eq", Some(2), Ok(not)), Status::False);
+    assert_eq!(printer.report("eq", None, Err(parse)), Status::Parse);
+    assert_eq!(
+        Status::True.max(Status::Usage).max(Status::False),
+        Status::Usage
+    );
+
+    let json = Printer {
+        json: true,
+        quiet: true,
+    };
+    let usage = anyhow!("the following required arguments were not provided: <right>");
+    assert_eq!(json.usage(Some("eq"), usage), Status::Usage);
+}
@@ -0,0 +1,120 @@
+use crate::{
+    output::Status,
+    repl::{
+        parse::{parse, Expr, Op, Statement},
+        Reply, Session,
+    },
+};
+use anyhow::Result;
+
+fn show(session: &mut Session, input: &str) -> Result<String> {
+    match session.line(input)? {
+        Reply::Show(text) => Ok(text),
+        reply => panic!("expected output from {input}, got {reply:?}"),
+    }
+}
+
+#[test]
+fn test_parse() -> Result<()> {
+    let word = |w: &str| Box::new(Expr::Word(w.to_string()));
+
+    assert_eq!(
+        parse("^1.2 & ~1.4 | >=2<3")?,
+        Statement::Expr(Expr::Binary(
+            Op::Or,
+            Box::new(Expr::Binary(Op::And, word("^1.2"), word("~1.4"))),
+            word(">=2<3"),
+        ))
+    );
+    assert_eq!(
+        parse("r = ^1,!=1.5")?,
+        Statement::Let("r".to_string(), Expr::Word("^1,!=1.5".to_string()))
+    );
+    assert_eq!(
+        parse(r#"max("^1,!=1.5", [1.0])"#)?,
+        Statement::Expr(Expr::Call(
+            "max".to_string(),
+            vec![
+                Expr::Text("^1,!=1.5".to_string()),
+                Expr::List(vec![Expr::Word("1.0".to_string())]),
+            ],
+        ))
+    );
+    assert_eq!(
+        parse("1.2.3-rc.1 in ^1 - ~1.2")?,
+        Statement::Expr(Expr::Binary(
+            Op::In,
+            word("1.2.3-rc.1"),
+            Box::new(Expr::Binary(Op::Minus, word("^1"), word("~1.2"))),
+        ))
+    );
+
+    assert!(parse("max(^1").is_err());
+    assert!(parse("^1 &").is_err());
+    assert!(parse("1.2 1.3").is_err());
+    assert!(parse("1.2 = 1.3").is_err());
+    assert!(parse(r#""^1"#).is_err());
+
+    // Deep nesting is an error, not a stack overflow.
+    let nested = |n: usize| format!("{}1.2.3{}", "(".repeat(n), ")".repeat(n));
+    assert!(parse(&nested(100)).is_ok());
+    assert!(parse(&nested(200_000)).is_err());
+    assert!(parse(&"[".repeat(200_000)).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_eval() -> Result<()> {
+    let mut session = Session::default();
+
+    assert_eq!(show(&mut session, "^1.2 & ~1.4")?, "~1.4");
+    assert_eq!(show(&mut session, "1.2.3 in >=1<2")?, "true");
+    assert_eq!(show(&mut session, "max(^1, [1.0, 1.5, 2.0])")?, "1.5.0");
+    assert_eq!(show(&mut session, "max(^3, [1.0])")?, "none");
+    assert_eq!(show(&mut session, "bump(1.2.3, minor)")?, "1.3.0");
+    assert_eq!(
+        show(&mut session, "bump(1.2.3, premajor, rc)")?,
+        "2.0.0-rc.0"
+    );
+    assert_eq!(
+        show(&mut session, "sort([2.0, 1.0, 2024.1])")?,
+        "[2024.1.0, 1.0.0, 2.0.0]"
+    );
+    assert_eq!(show(&mut session, "uniq([1, 1.0, 2])")?, "[1.0.0, 2.0.0]");
+    assert_eq!(show(&mut session, "compare(1.2, 1.10)")?, "-1");
+    assert_eq!(show(&mut session, "1.2 == 1.2.0")?, "true");
+    assert_eq!(show(&mut session, "~1.2 == >=1.2<1.3")?, "true");
+    assert_eq!(show(&mut session, "1.2.3 in [1.2.3, 2]")?, "true");
+
+    assert!(session.line("1.2.x").is_err());
+    assert!(session.line("foo").is_err());
+    assert!(session.line("max(^1)").is_err());
+    assert!(session.line("^1 < 2").is_err());
+    assert!(session.line("bump(1.2.3, sideways)").is_err());
+    let e = session.line("bump(1.2.3, 99999999999999)").unwrap_err();
+    assert_eq!(Status::of(&e), Status::Bump);
+
+    Ok(())
+}
+
+#[test]
+fn test_session() -> Result<()> {
+    let mut session = Session::default();
+
+    assert_eq!(session.line("  ")?, Reply::Nothing);
+    assert_eq!(show(&mut session, "r = ^1 | ^3")?, "r = ^1,^3");
+    assert_eq!(show(&mut session, "2.5 in r")?, "false");
+    assert_eq!(show(&mut session, "_")?, "false");
+    assert_eq!(show(&mut session, ":vars")?, "_ = false\nr = ^1,^3");
+    assert_eq!(show(&mut session, "!1")?, "r = ^1,^3");
+    assert_eq!(show(&mut session, "!!")?, "r = ^1,^3");
+    assert_eq!(show(&mut session, "!=1.2")?, "!=1.2");
+    assert!(session.line("!99").is_err());
+    assert!(show(&mut session, ":history")?.starts_with("   1  r = ^1 | ^3\n"));
+    assert!(show(&mut session, ":help")?.contains("max(range, [versions])"));
+    assert!(session.line(":nope").is_err());
+    assert_eq!(session.line(":quit")?, Reply::Quit);
+
+    Ok(())
+}
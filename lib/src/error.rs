@@ -1,3 +1,4 @@
+use std::{fmt, ops};
 use thiserror::Error;
 
 /// Errors from parsing and manipulating semantic versions and ranges.
@@ -7,6 +8,152 @@ pub enum Error {
     Semver(String),
     #[error("invalid range: {0}")]
     Range(String),
+    /// A well-formed version that can't be bumped as asked, eg. past the
+    /// largest number a component holds.
+    #[error("cannot bump: {0}")]
+    Bump(String),
+    #[error("{0}")]
+    Parse(Diagnostic),
+}
+
+impl Error {
+    /// A multi-line, caret-underlined rendering of the error for terminals.
+    /// Errors without a location render as their message.
+    pub fn render(&self) -> String {
+        match self {
+            Error::Parse(d) => d.render(),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Re-anchors a diagnostic produced for a substring onto the input it was
+    /// sliced from, `offset` bytes in.
+    pub(crate) fn within(self, input: &str, offset: usize) -> Self {
+        match self {
+            Error::Parse(d) => Error::Parse(Diagnostic {
+                kind: d.kind,
+                span: d.span.start + offset..d.span.end + offset,
+                input: input.to_string(),
+            }),
+            e => e,
+        }
+    }
+}
+
+/// What went wrong while parsing, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub kind: ErrorKind,
+    /// Byte offsets of the offending text within `input`.
+    pub span: ops::Range<usize>,
+    pub input: String,
+}
+
+impl Diagnostic {
+    pub fn new(kind: ErrorKind, span: ops::Range<usize>, input: &str) -> Self {
+        Self {
+            kind,
+            span,
+            input: input.to_string(),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let col = self.input[..self.span.start].chars().count();
+        let width = self.input[self.span.clone()].chars().count().max(1);
+        format!(
+            "{}\n  {}\n  {}{}",
+            self.kind,
+            self.input,
+            " ".repeat(col),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at byte {} of '{}'",
+            self.kind, self.span.start, self.input
+        )
+    }
+}
+
+/// The kinds of problem a [`Diagnostic`] can point at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A number (or numeric prerelease identifier) starting with `0`.
+    LeadingZero(Part),
+    /// Nothing between two separators, or after a `-`/`+`.
+    EmptyIdentifier(Part),
+    /// A character that isn't allowed where it appears.
+    UnknownCharacter(char),
+    /// A number that doesn't fit in a `usize`.
+    Overflow(Part),
+    /// Strict mode: anything other than exactly three components.
+    ComponentCount(usize),
+    /// Strict mode: a leading `v`.
+    Prefix,
+    /// A range constraint with an operator the grammar doesn't know.
+    BadOperator(String),
+    /// A range constraint whose lower bound isn't below its upper bound.
+    InvertedBounds,
+    /// Nothing between two range separators.
+    EmptyConstraint,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::LeadingZero(p) => write!(f, "leading zero in {p}"),
+            ErrorKind::EmptyIdentifier(p @ (Part::Prerelease | Part::Build)) => {
+                write!(f, "empty {p} identifier")
+            }
+            ErrorKind::EmptyIdentifier(p) => write!(f, "{p} is empty"),
+            ErrorKind::UnknownCharacter(c) => write!(f, "unexpected character '{c}'"),
+            ErrorKind::Overflow(p) => write!(f, "{p} is too large"),
+            ErrorKind::ComponentCount(n) => {
+                write!(f, "expected MAJOR.MINOR.PATCH, found {n} component(s)")
+            }
+            ErrorKind::Prefix => write!(f, "'v' prefix is not allowed"),
+            ErrorKind::BadOperator(op) if op.is_empty() => write!(f, "missing operator"),
+            ErrorKind::BadOperator(op) => write!(f, "unknown operator '{op}'"),
+            ErrorKind::InvertedBounds => write!(f, "lower bound is not below upper bound"),
+            ErrorKind::EmptyConstraint => write!(f, "empty constraint"),
+        }
+    }
+}
+
+/// The part of a version a [`ErrorKind`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    Major,
+    Minor,
+    Patch,
+    Component,
+    Prerelease,
+    Build,
+    Epoch,
+    /// A Debian revision or RPM release.
+    Revision,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Part::Major => "major",
+            Part::Minor => "minor",
+            Part::Patch => "patch",
+            Part::Component => "version component",
+            Part::Prerelease => "prerelease",
+            Part::Build => "build",
+            Part::Epoch => "epoch",
+            Part::Revision => "revision",
+        };
+        write!(f, "{s}")
+    }
 }
 
 /// Convenience alias for results returning [`Error`].
@@ -2,7 +2,7 @@ mod error;
 pub mod range;
 pub mod semver;
 
-pub use error::{Error, Result};
+pub use error::{Diagnostic, Error, ErrorKind, Part, Result};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,17 @@
+use super::{interval, Range};
+
+impl Range {
+    /// Versions not satisfying `self`, taking `0.0.0` as the lowest version.
+    ///
+    /// ```
+    /// use libsemverator::range::Range;
+    ///
+    /// let rest = Range::parse("=1.0.0")?.complement();
+    /// assert_eq!(rest.raw, "!=1.0.0");
+    /// assert_eq!(Range::parse(&rest.raw)?, rest);
+    /// # Ok::<(), libsemverator::Error>(())
+    /// ```
+    pub fn complement(&self) -> Range {
+        Range::from_intervals(interval::complement(&self.intervals()))
+    }
+}
@@ -0,0 +1,191 @@
+//! Cargo version requirements, as found in `Cargo.toml`.
+//!
+//! Supports comma-separated comparators using `=`, `>`, `>=`, `<`, `<=`, `~`
+//! and `^`, partial versions, and wildcards (`*`, `1.*`, `1.2.*`). A version
+//! without an operator means `^`, unless it has a wildcard.
+//!
+//! Cargo only lets a prerelease match if some comparator names a prerelease
+//! of the same `MAJOR.MINOR.PATCH`. A [`Range`] from [`Range::parse_cargo`]
+//! keeps those comparators in its bounds, so [`OPTIONS`] applies the rule
+//! through [`Range::satisfies_with`]; plain [`Range::satisfies`] matches
+//! prereleases by precedence.
+
+use std::ops::Bound;
+
+use super::comparator::{comparator, partial, Partial};
+use crate::{
+    error::{Diagnostic, Error, ErrorKind, Result},
+    range::{
+        interval::{self, Interval},
+        satisfies::{PrereleasePolicy, SatisfyOptions},
+        Range,
+    },
+    semver::Semver,
+};
+
+const OPERATORS: [&str; 8] = ["", "=", ">", ">=", "<", "<=", "~", "^"];
+
+/// Cargo's prerelease rule, for [`Range::satisfies_with`] and friends on a
+/// range from [`Range::parse_cargo`].
+pub const OPTIONS: SatisfyOptions = SatisfyOptions {
+    include_prerelease: PrereleasePolicy::SameTuple,
+};
+
+impl Range {
+    /// Parses a Cargo version requirement into an equivalent [`Range`]. See
+    /// [`range::dialect::cargo`](crate::range::dialect::cargo) for what is
+    /// supported.
+    ///
+    /// `.satisfies` on the result matches prereleases by precedence, so
+    /// `^1.2` takes `1.3.0-beta`. Pass [`OPTIONS`] to `.satisfies_with` for
+    /// Cargo's rule, which doesn't.
+    pub fn parse_cargo(req: &str) -> Result<Range> {
+        let mut rv = vec![Interval::everything()];
+        for (op, p, at) in comparators(req)? {
+            rv = interval::intersect(&rv, &comparator(req, op, &p, at)?);
+        }
+        Ok(Range::from_intervals(rv))
+    }
+
+    /// Writes the range as a Cargo version requirement. Fails for unions of
+    /// disjoint ranges and for versions with more than three components,
+    /// which Cargo can't express.
+    pub fn to_cargo(&self) -> Result<String> {
+        let fail = |why: String| {
+            Err(Error::Range(format!(
+                "cannot write {self} as a Cargo requirement: {why}"
+            )))
+        };
+
+        let intervals = self.intervals();
+        let [i] = intervals.as_slice() else {
+            return match intervals.len() {
+                0 => Ok("<0.0.0".to_string()),
+                n => fail(format!("it is a union of {n} ranges")),
+            };
+        };
+
+        let mut rv = Vec::new();
+        match (&i.lo, &i.hi) {
+            (Bound::Included(lo), Bound::Included(hi)) if lo == hi => {
+                return Ok(format!("={}", version(lo)?));
+            }
+            (Bound::Included(lo), Bound::Excluded(hi))
+                if !is_zero(lo) && caret(lo).is_ok_and(|c| *hi == c) =>
+            {
+                return Ok(format!("^{}", version(lo)?));
+            }
+            (Bound::Included(lo), Bound::Excluded(hi))
+                if !is_zero(lo) && tilde(lo).is_ok_and(|c| *hi == c) =>
+            {
+                return Ok(format!("~{}", version(lo)?));
+            }
+            (Bound::Included(lo), _) if is_zero(lo) => {}
+            (Bound::Included(lo), _) => rv.push(format!(">={}", version(lo)?)),
+            (Bound::Excluded(lo), _) => rv.push(format!(">{}", version(lo)?)),
+            (Bound::Unbounded, _) => {}
+        }
+        match &i.hi {
+            Bound::Included(hi) => rv.push(format!("<={}", version(hi)?)),
+            Bound::Excluded(hi) => rv.push(format!("<{}", version(hi)?)),
+            Bound::Unbounded => {}
+        }
+
+        if rv.is_empty() {
+            return Ok("*".to_string());
+        }
+        Ok(rv.join(", "))
+    }
+}
+
+/// Whether `version` meets the Cargo requirement `req`, including Cargo's
+/// rule that prereleases only match comparators naming a prerelease of the
+/// same `MAJOR.MINOR.PATCH`.
+pub fn satisfies(req: &str, version: &Semver) -> Result<bool> {
+    Ok(Range::parse_cargo(req)?.satisfies_with(version, &OPTIONS))
+}
+
+// The comparators of `req`, with their operators made explicit and their
+// offsets into `req`.
+fn comparators(req: &str) -> Result<Vec<(&'static str, Partial, usize)>> {
+    let mut rv = Vec::new();
+    let mut start = 0;
+    for piece in req.split(',') {
+        let trimmed = piece.trim_start();
+        let at = start + piece.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        start += piece.len() + 1;
+
+        if trimmed.is_empty() {
+            return Err(Error::Parse(Diagnostic::new(
+                ErrorKind::EmptyConstraint,
+                at..at,
+                req,
+            )));
+        }
+
+        let op_len = trimmed
+            .find(|c| !"<>=~^".contains(c))
+            .unwrap_or(trimmed.len());
+        let Some(op) = OPERATORS.into_iter().find(|op| *op == &trimmed[..op_len]) else {
+            return Err(Error::Parse(Diagnostic::new(
+                ErrorKind::BadOperator(trimmed[..op_len].to_string()),
+                at..at + op_len,
+                req,
+            )));
+        };
+        let version = trimmed[op_len..].trim_start();
+        let p = partial(req, version, at + trimmed.len() - version.len())?;
+        let op = match op {
+            "" if version.contains(['*', 'x', 'X']) => "=",
+            "" => "^",
+            op => op,
+        };
+        rv.push((op, p, at));
+    }
+    Ok(rv)
+}
+
+// `MAJOR.MINOR.PATCH[-PRERELEASE]`, as Cargo requires.
+fn version(v: &Semver) -> Result<String> {
+    if v.components.len() > 3 {
+        return Err(Error::Range(format!(
+            "cannot write {} as a Cargo version: it has more than three components",
+            v.raw
+        )));
+    }
+    let mut rv = format!("{}.{}.{}", v.major, v.minor, v.patch);
+    if !v.prerelease.is_empty() {
+        let pr = v
+            .prerelease
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        rv.push_str(&format!("-{pr}"));
+    }
+    Ok(rv)
+}
+
+fn is_zero(v: &Semver) -> bool {
+    v.components.iter().all(|c| *c == 0) && v.prerelease.is_empty()
+}
+
+// The exclusive upper bounds of `^v` and `~v`, if there's a version above
+// them.
+fn caret(v: &Semver) -> Result<Semver> {
+    match (v.major, v.minor) {
+        (0, 0) => Semver::from((0, 0, succ(v, v.patch)?)),
+        (0, minor) => Semver::from((0, succ(v, minor)?, 0)),
+        (major, _) => Semver::from((succ(v, major)?, 0, 0)),
+    }
+}
+
+fn tilde(v: &Semver) -> Result<Semver> {
+    Semver::from((v.major, succ(v, v.minor)?, 0))
+}
+
+fn succ(v: &Semver, n: usize) -> Result<usize> {
+    n.checked_add(1)
+        .ok_or_else(|| Error::Range(format!("no version follows {} to bound it", v.raw)))
+}
@@ -0,0 +1,200 @@
+//! Comparators on partial versions (`1`, `1.2.x`, `1.2.3-pre`), which the
+//! npm and Cargo grammars share.
+
+use std::ops::{self, Bound};
+
+use crate::{
+    error::{Diagnostic, Error, ErrorKind, Part, Result},
+    range::interval::Interval,
+    semver::Semver,
+};
+
+// The intervals matching one comparator (none, or one).
+pub(super) fn comparator(range: &str, op: &str, p: &Partial, at: usize) -> Result<Vec<Interval>> {
+    let everything = Interval::everything();
+    let Some(major) = p.major else {
+        // `*` and friends: every comparator is all or nothing.
+        return Ok(match op {
+            "<" | ">" => vec![],
+            _ => vec![everything],
+        });
+    };
+
+    let floor = p.floor()?;
+    let between = |lo: Semver, hi: Semver| Interval {
+        lo: Bound::Included(lo),
+        hi: Bound::Excluded(hi),
+    };
+    let interval = match op {
+        "" | "=" if p.is_full() => Interval {
+            lo: Bound::Included(floor.clone()),
+            hi: Bound::Included(floor),
+        },
+        "" | "=" => between(floor, p.next()?),
+        ">=" => Interval {
+            lo: Bound::Included(floor),
+            hi: Bound::Unbounded,
+        },
+        ">" if p.is_full() => Interval {
+            lo: Bound::Excluded(floor),
+            hi: Bound::Unbounded,
+        },
+        ">" => Interval {
+            lo: Bound::Included(p.next()?),
+            hi: Bound::Unbounded,
+        },
+        "<" => Interval {
+            lo: everything.lo,
+            hi: Bound::Excluded(floor),
+        },
+        "<=" if p.is_full() => Interval {
+            lo: everything.lo,
+            hi: Bound::Included(floor),
+        },
+        "<=" => Interval {
+            lo: everything.lo,
+            hi: Bound::Excluded(p.next()?),
+        },
+        "~" | "~>" => match p.minor {
+            Some(minor) => between(floor, version(major, p.succ(minor)?, 0)?),
+            None => between(floor, version(p.succ(major)?, 0, 0)?),
+        },
+        "^" => match (major, p.minor, p.patch) {
+            (0, Some(0), Some(patch)) => between(floor, version(0, 0, p.succ(patch)?)?),
+            (0, Some(minor), _) => between(floor, version(0, p.succ(minor)?, 0)?),
+            _ => between(floor, version(p.succ(major)?, 0, 0)?),
+        },
+        _ => {
+            return Err(Error::Parse(Diagnostic::new(
+                ErrorKind::BadOperator(op.to_string()),
+                at..at + op.len(),
+                range,
+            )))
+        }
+    };
+    Ok(interval
+        .intersect(&Interval::everything())
+        .into_iter()
+        .collect())
+}
+
+fn version(major: usize, minor: usize, patch: usize) -> Result<Semver> {
+    Semver::parse(&format!("{major}.{minor}.{patch}"))
+}
+
+// A version with trailing components possibly missing or wildcards (`x`,
+// `X`, `*`). Anything after a wildcard is ignored, like node-semver does.
+#[derive(Debug, Default)]
+pub(super) struct Partial {
+    pub major: Option<usize>,
+    pub minor: Option<usize>,
+    pub patch: Option<usize>,
+    // `-pre+build`, only allowed on full versions.
+    pub qualifier: String,
+    // Where the numbers were written, for diagnostics.
+    pub span: ops::Range<usize>,
+    pub input: String,
+}
+
+impl Partial {
+    pub fn is_full(&self) -> bool {
+        self.patch.is_some()
+    }
+
+    // The lowest version the partial covers.
+    pub fn floor(&self) -> Result<Semver> {
+        let v = format!(
+            "{}.{}.{}{}",
+            self.major.unwrap_or(0),
+            self.minor.unwrap_or(0),
+            self.patch.unwrap_or(0),
+            self.qualifier
+        );
+        Semver::parse(&v)
+    }
+
+    // The lowest version above everything the partial covers.
+    pub fn next(&self) -> Result<Semver> {
+        match (self.major, self.minor) {
+            (Some(major), None) => version(self.succ(major)?, 0, 0),
+            (Some(major), Some(minor)) => version(major, self.succ(minor)?, 0),
+            (None, _) => Ok(Semver::infinty()),
+        }
+    }
+
+    // `n + 1`, or an error pointing at the partial if that doesn't fit.
+    fn succ(&self, n: usize) -> Result<usize> {
+        n.checked_add(1).ok_or_else(|| {
+            Error::Parse(Diagnostic::new(
+                ErrorKind::Overflow(Part::Component),
+                self.span.clone(),
+                &self.input,
+            ))
+        })
+    }
+}
+
+// Parses `text`, found at `start` in `range`, as a partial version.
+pub(super) fn partial(range: &str, text: &str, start: usize) -> Result<Partial> {
+    let fail = |kind, at: usize, len: usize| {
+        Err(Error::Parse(Diagnostic::new(
+            kind,
+            start + at..start + at + len,
+            range,
+        )))
+    };
+    let unexpected = |at: usize| match text[at..].chars().next() {
+        Some(c) => fail(ErrorKind::UnknownCharacter(c), at, c.len_utf8()),
+        None => fail(ErrorKind::EmptyIdentifier(Part::Component), at, 0),
+    };
+
+    let bytes = text.as_bytes();
+    let mut i = usize::from(bytes.first() == Some(&b'v'));
+    let mut parts = Vec::new();
+    let mut wildcard = false;
+    loop {
+        let at = i;
+        if bytes.get(i).is_some_and(|b| b"xX*".contains(b)) {
+            i += 1;
+            wildcard = true;
+            parts.push(None);
+        } else {
+            while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+                i += 1;
+            }
+            if i == at {
+                return unexpected(i);
+            }
+            let n = text[at..i].parse::<usize>();
+            match n {
+                Ok(n) if !wildcard => parts.push(Some(n)),
+                Ok(_) => parts.push(None),
+                Err(_) => return fail(ErrorKind::Overflow(Part::Component), at, i - at),
+            }
+        }
+        if parts.len() == 3 || bytes.get(i) != Some(&b'.') {
+            break;
+        }
+        i += 1;
+    }
+
+    let qualifier = &text[i..];
+    if !qualifier.is_empty() {
+        let valid = parts.len() == 3
+            && !wildcard
+            && qualifier.starts_with(['-', '+'])
+            && Semver::parse(&format!("0.0.0{qualifier}")).is_ok();
+        if !valid {
+            return unexpected(i);
+        }
+    }
+
+    Ok(Partial {
+        major: parts.first().copied().flatten(),
+        minor: parts.get(1).copied().flatten(),
+        patch: parts.get(2).copied().flatten(),
+        qualifier: qualifier.to_string(),
+        span: start..start + i,
+        input: range.to_string(),
+    })
+}
@@ -0,0 +1,10 @@
+//! Range syntaxes other than pkgx's. npm and Cargo ranges are parsed into
+//! (and for Cargo, written back from) [`Range`]; PEP 440 specifiers work on
+//! their own version type and have their own set type.
+//!
+//! [`Range`]: super::Range
+
+pub mod cargo;
+mod comparator;
+pub mod npm;
+pub mod pep440;
@@ -0,0 +1,93 @@
+//! node-semver ranges, as found in `package.json`.
+//!
+//! Supports `||` unions of space-separated comparator sets, hyphen ranges
+//! (`1.2 - 2.3.4`), X-ranges (`1.x`, `1.2.*`, `*`), partial versions, and the
+//! `<`, `<=`, `>`, `>=`, `=`, `~`, `~>` and `^` operators.
+//!
+//! Prereleases are compared by precedence, as [`Range::satisfies`] always
+//! does; node-semver's extra rule hiding prereleases from most comparators
+//! is not applied.
+
+use std::ops::Bound;
+
+use super::comparator::{comparator, partial};
+use crate::{
+    error::Result,
+    range::{
+        interval::{self, Interval},
+        Range,
+    },
+};
+
+/// Parses a node-semver range into an equivalent [`Range`].
+pub fn parse(range: &str) -> Result<Range> {
+    let mut sets = Vec::new();
+    let mut start = 0;
+    for (i, _) in range.match_indices("||") {
+        sets.extend(comparator_set(range, start, i)?);
+        start = i + 2;
+    }
+    sets.extend(comparator_set(range, start, range.len())?);
+
+    Ok(Range::from_intervals(interval::union(sets)))
+}
+
+// The intervals matched by the space-separated comparators in
+// `range[start..end]`: empty when they contradict each other.
+fn comparator_set(range: &str, start: usize, end: usize) -> Result<Vec<Interval>> {
+    let text = &range[start..end];
+    let trimmed = text.trim_start();
+    let start = start + text.len() - trimmed.len();
+    let text = trimmed.trim_end();
+
+    if let Some(dash) = text.find(" - ") {
+        let left = text[..dash].trim_end();
+        let right = text[dash + 3..].trim_start();
+        let right_start = start + text.len() - right.len();
+        let lo = partial(range, left, start)?;
+        let hi = partial(range, right, right_start)?;
+        let interval = Interval {
+            lo: Bound::Included(lo.floor()?),
+            hi: match (hi.major, hi.patch) {
+                (None, _) => Bound::Unbounded,
+                (_, Some(_)) => Bound::Included(hi.floor()?),
+                _ => Bound::Excluded(hi.next()?),
+            },
+        };
+        return Ok(interval
+            .intersect(&Interval::everything())
+            .into_iter()
+            .collect());
+    }
+
+    let mut rv = vec![Interval::everything()];
+    let mut words = words(text, start).into_iter();
+    while let Some((word, at)) = words.next() {
+        let op_len = word.find(|c| !"<>=~^".contains(c)).unwrap_or(word.len());
+        let (op, version, version_at) = if op_len == word.len() {
+            // An operator on its own, eg. `>= 1.2.3`.
+            match words.next() {
+                Some((next, next_at)) => (word, next, next_at),
+                None => (word, "", at + word.len()),
+            }
+        } else {
+            (&word[..op_len], &word[op_len..], at + op_len)
+        };
+        let p = partial(range, version, version_at)?;
+        let c = comparator(range, op, &p, at)?;
+        rv = interval::intersect(&rv, &c);
+    }
+    Ok(rv)
+}
+
+// Whitespace-separated words of `text`, with their offsets into the range.
+fn words(text: &str, start: usize) -> Vec<(&str, usize)> {
+    let mut rv = Vec::new();
+    let mut offset = 0;
+    for word in text.split_whitespace() {
+        let at = offset + text[offset..].find(word).unwrap_or(0);
+        rv.push((word, start + at));
+        offset = at + word.len();
+    }
+    rv
+}
@@ -0,0 +1,254 @@
+//! Python version specifiers, per [PEP 440].
+//!
+//! A specifier set is comma-separated clauses, all of which must match:
+//! `~=`, `==` and `!=` (either with a trailing `.*`), `<`, `<=`, `>`, `>=`
+//! and `===`. These work on [`Version`]s rather than [`Semver`]s, so unlike
+//! the other dialects they are not lowered onto a [`Range`].
+//!
+//! [PEP 440]: https://peps.python.org/pep-0440/#version-specifiers
+//! [`Semver`]: crate::semver::Semver
+//! [`Range`]: crate::range::Range
+
+use std::fmt;
+
+use crate::{
+    error::{Diagnostic, Error, ErrorKind, Result},
+    semver::dialect::pep440::Version,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// `~=`
+    Compatible,
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterEqual,
+    /// `===`
+    Arbitrary,
+}
+
+// Longest first, so `===` isn't read as `==` and so on.
+const OPERATORS: [(&str, Operator); 8] = [
+    ("===", Operator::Arbitrary),
+    ("~=", Operator::Compatible),
+    ("==", Operator::Equal),
+    ("!=", Operator::NotEqual),
+    ("<=", Operator::LessEqual),
+    (">=", Operator::GreaterEqual),
+    ("<", Operator::Less),
+    (">", Operator::Greater),
+];
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (s, _) = OPERATORS
+            .iter()
+            .find(|(_, op)| op == self)
+            .expect("every operator is listed");
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Specifier {
+    pub op: Operator,
+    pub version: Version,
+    /// Whether the version ended in `.*`, for `==` and `!=`.
+    pub wildcard: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpecifierSet {
+    pub raw: String,
+    pub specifiers: Vec<Specifier>,
+}
+
+impl SpecifierSet {
+    /// Parses a specifier set such as `>=1.0,!=1.5.*,<2`. An empty string
+    /// matches every version.
+    pub fn parse(specifiers: &str) -> Result<Self> {
+        let mut rv = Vec::new();
+        let mut start = 0;
+        if !specifiers.trim().is_empty() {
+            for clause in specifiers.split(',') {
+                rv.push(Specifier::parse(clause).map_err(|e| e.within(specifiers, start))?);
+                start += clause.len() + 1;
+            }
+        }
+        Ok(Self {
+            raw: specifiers.to_string(),
+            specifiers: rv,
+        })
+    }
+
+    /// Whether `version` matches every specifier. Pre-releases only match
+    /// if a specifier names one, as PEP 440 recommends.
+    pub fn satisfies(&self, version: &Version) -> bool {
+        (!version.is_prerelease() || self.allows_prereleases()) && self.matches(version)
+    }
+
+    /// The highest of `versions` that satisfies the set. If none do, falls
+    /// back to the highest matching pre-release, as PEP 440 allows when
+    /// nothing else is available.
+    pub fn max<'a>(&self, versions: impl IntoIterator<Item = &'a Version>) -> Option<Version> {
+        self.filter(versions).into_iter().max().cloned()
+    }
+
+    /// The lowest of `versions` that satisfies the set, falling back to
+    /// pre-releases as [`SpecifierSet::max`] does.
+    pub fn min<'a>(&self, versions: impl IntoIterator<Item = &'a Version>) -> Option<Version> {
+        self.filter(versions).into_iter().min().cloned()
+    }
+
+    /// The `versions` that satisfy the set, in their original order, or the
+    /// matching pre-releases if none do.
+    pub fn filter<'a>(&self, versions: impl IntoIterator<Item = &'a Version>) -> Vec<&'a Version> {
+        let matching = versions
+            .into_iter()
+            .filter(|v| self.matches(v))
+            .collect::<Vec<_>>();
+        if matching.iter().any(|v| self.satisfies(v)) {
+            matching.into_iter().filter(|v| self.satisfies(v)).collect()
+        } else {
+            matching
+        }
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        self.specifiers.iter().all(|s| s.matches(version))
+    }
+
+    fn allows_prereleases(&self) -> bool {
+        self.specifiers
+            .iter()
+            .any(|s| s.op != Operator::NotEqual && s.version.is_prerelease())
+    }
+}
+
+impl fmt::Display for SpecifierSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rv = self
+            .specifiers
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", rv.join(","))
+    }
+}
+
+impl Specifier {
+    fn parse(clause: &str) -> Result<Self> {
+        let trimmed = clause.trim_start();
+        let at = clause.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        let fail = |kind, span: std::ops::Range<usize>| {
+            Err(Error::Parse(Diagnostic::new(kind, span, clause)))
+        };
+
+        if trimmed.is_empty() {
+            return fail(ErrorKind::EmptyConstraint, at..at);
+        }
+        let Some((text, op)) = OPERATORS.iter().find(|(s, _)| trimmed.starts_with(s)) else {
+            let len = trimmed
+                .find(|c: char| !"<>=!~".contains(c))
+                .unwrap_or(trimmed.len());
+            return fail(
+                ErrorKind::BadOperator(trimmed[..len].to_string()),
+                at..at + len,
+            );
+        };
+
+        let rest = &trimmed[text.len()..];
+        let (rest, wildcard) = match rest.strip_suffix(".*") {
+            Some(rest) => (rest, true),
+            None => (rest, false),
+        };
+        let offset = at + text.len();
+        let version = Version::parse(rest).map_err(|e| e.within(clause, offset))?;
+
+        let invalid = |why: &str| Err(Error::Range(format!("{}: {why}", trimmed)));
+        match op {
+            Operator::Equal | Operator::NotEqual if wildcard && version != version.base() => {
+                return invalid("only a release can end in .*");
+            }
+            Operator::Equal | Operator::NotEqual if wildcard => {}
+            _ if wildcard => return invalid("only == and != can end in .*"),
+            Operator::Compatible if version.release.len() < 2 => {
+                return invalid("~= needs at least two release segments");
+            }
+            Operator::Equal | Operator::NotEqual | Operator::Arbitrary => {}
+            _ if !version.local.is_empty() => {
+                return invalid("only ==, != and === allow local versions");
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            op: *op,
+            version,
+            wildcard,
+        })
+    }
+
+    pub fn matches(&self, candidate: &Version) -> bool {
+        let spec = &self.version;
+        let public = candidate.public();
+        match self.op {
+            Operator::Compatible => {
+                let prefix = &spec.release[..spec.release.len() - 1];
+                public >= *spec && starts_with(candidate, spec.epoch, prefix)
+            }
+            Operator::Equal if self.wildcard => starts_with(candidate, spec.epoch, &spec.release),
+            Operator::Equal if spec.local.is_empty() => public == *spec,
+            Operator::Equal => candidate == spec,
+            Operator::NotEqual => !Self {
+                op: Operator::Equal,
+                ..self.clone()
+            }
+            .matches(candidate),
+            Operator::LessEqual => public <= *spec,
+            Operator::GreaterEqual => public >= *spec,
+            // `<1.0` doesn't let `1.0rc1` in, unless it's `<1.0rc2`.
+            Operator::Less => {
+                public < *spec
+                    && (spec.is_prerelease()
+                        || !candidate.is_prerelease()
+                        || candidate.base() != spec.base())
+            }
+            // `>1.0` doesn't let `1.0.post1` or `1.0+local` in.
+            Operator::Greater => {
+                public > *spec
+                    && (spec.is_postrelease()
+                        || !candidate.is_postrelease()
+                        || candidate.base() != spec.base())
+                    && (candidate.local.is_empty() || candidate.base() != spec.base())
+            }
+            Operator::Arbitrary => candidate.raw.eq_ignore_ascii_case(&spec.raw),
+        }
+    }
+}
+
+// Whether `version`'s release, padded with zeros, starts with `prefix`.
+fn starts_with(version: &Version, epoch: u64, prefix: &[u64]) -> bool {
+    version.epoch == epoch
+        && prefix
+            .iter()
+            .enumerate()
+            .all(|(i, n)| version.release.get(i).copied().unwrap_or(0) == *n)
+}
+
+impl fmt::Display for Specifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let wildcard = if self.wildcard { ".*" } else { "" };
+        write!(f, "{}{}{wildcard}", self.op, self.version)
+    }
+}
@@ -0,0 +1,17 @@
+use super::{interval, Range};
+
+impl Range {
+    /// Versions satisfying `self` but not `range`.
+    ///
+    /// ```
+    /// use libsemverator::range::Range;
+    ///
+    /// let rest = Range::parse("^1")?.difference(&Range::parse("=1.0.0")?);
+    /// assert_eq!(rest.raw, ">1.0.0<2.0.0");
+    /// # Ok::<(), libsemverator::Error>(())
+    /// ```
+    pub fn difference(&self, range: &Range) -> Range {
+        let rest = interval::complement(&range.intervals());
+        Range::from_intervals(interval::intersect(&self.intervals(), &rest))
+    }
+}
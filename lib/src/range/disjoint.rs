@@ -0,0 +1,13 @@
+use super::{interval, Range};
+
+impl Range {
+    /// Whether no version satisfies both ranges.
+    pub fn is_disjoint(&self, range: &Range) -> bool {
+        interval::intersect(&self.intervals(), &range.intervals()).is_empty()
+    }
+
+    /// Whether some version satisfies both ranges.
+    pub fn overlaps(&self, range: &Range) -> bool {
+        !self.is_disjoint(range)
+    }
+}
@@ -0,0 +1,40 @@
+use crate::semver::Semver;
+
+use super::{satisfies::SatisfyOptions, Range};
+
+impl Range {
+    /// The versions that satisfy the range, in their original order.
+    pub fn filter<'a, I>(&'a self, semvers: I) -> impl Iterator<Item = &'a Semver> + 'a
+    where
+        I: IntoIterator<Item = &'a Semver>,
+        I::IntoIter: 'a,
+    {
+        semvers.into_iter().filter(|s| self.satisfies(s))
+    }
+
+    /// [`Range::filter`], with `options` deciding which prereleases match.
+    pub fn filter_with<'a, I>(
+        &'a self,
+        semvers: I,
+        options: &'a SatisfyOptions,
+    ) -> impl Iterator<Item = &'a Semver> + 'a
+    where
+        I: IntoIterator<Item = &'a Semver>,
+        I::IntoIter: 'a,
+    {
+        semvers
+            .into_iter()
+            .filter(|s| self.satisfies_with(s, options))
+    }
+
+    /// The versions that satisfy the range, lowest first.
+    pub fn sorted_matches<'a>(&self, semvers: impl IntoIterator<Item = &'a Semver>) -> Vec<Semver> {
+        let mut rv = semvers
+            .into_iter()
+            .filter(|s| self.satisfies(s))
+            .cloned()
+            .collect::<Vec<_>>();
+        rv.sort();
+        rv
+    }
+}
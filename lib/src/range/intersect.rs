@@ -1,51 +1,25 @@
-use super::{Constraint, Range};
-use crate::error::{Error, Result};
+use super::{
+    interval::{self, Interval},
+    Range,
+};
+use crate::error::Result;
 
 impl Range {
+    /// Versions satisfying both ranges, as sorted, non-overlapping
+    /// constraints. Disjoint ranges give [`Range::none`].
     pub fn intersect(&self, range: &Range) -> Result<Range> {
-        let a = self;
-        let b = range;
-
-        let mut set = Vec::<Constraint>::new();
+        Ok(Range::from_intervals(interval::intersect(
+            &self.intervals(),
+            &range.intervals(),
+        )))
+    }
 
-        for aa in a.set.iter() {
-            for bb in b.set.iter() {
-                match (aa, bb) {
-                    (Constraint::Any, _) => return Range::parse(&b.raw),
-                    (_, Constraint::Any) => return Range::parse(&a.raw),
-                    (Constraint::Single(aaa), Constraint::Single(bbb)) => {
-                        if aaa.eq(bbb) {
-                            set.push(aa.clone());
-                        }
-                    }
-                    (Constraint::Single(aaa), Constraint::Contiguous(_, _)) => {
-                        if bb.satisfies(aaa) {
-                            set.push(aa.clone());
-                        }
-                    }
-                    (Constraint::Contiguous(_, _), Constraint::Single(bbb)) => {
-                        if aa.satisfies(bbb) {
-                            set.push(bb.clone());
-                        }
-                    }
-                    (Constraint::Contiguous(a1, a2), Constraint::Contiguous(b1, b2)) => {
-                        if a1.lt(b2) && b1.lt(a2) {
-                            let v1 = if a1.gt(b1) { a1 } else { b1 };
-                            let v2 = if a2.lt(b2) { a2 } else { b2 };
-                            set.push(Constraint::Contiguous(v1.clone(), v2.clone()));
-                        }
-                    }
-                }
-            }
-        }
-        if set.is_empty() {
-            return Err(Error::Range("no intersection possible".into()));
-        }
-        let mut rv = Range {
-            raw: "".to_string(),
-            set,
-        };
-        rv.raw = rv.raw();
-        Ok(rv)
+    /// Versions satisfying every range, eg. when resolving one dependency
+    /// for many dependents. No ranges at all intersect to `*`.
+    pub fn intersect_all(ranges: &[Range]) -> Range {
+        let rv = ranges.iter().fold(vec![Interval::everything()], |acc, r| {
+            interval::intersect(&acc, &r.intervals())
+        });
+        Range::from_intervals(rv)
     }
 }
@@ -0,0 +1,257 @@
+//! Interval arithmetic over [`Constraint`]s.
+//!
+//! Every constraint is a contiguous interval of versions: `=v` is `[v, v]`,
+//! `>=a<b` is `[a, b)`, `>a<=b` is `(a, b]` and `*` is `[0, ∞)`, while `!=v`
+//! takes `[v, v]` away.
+//! Set operations on ranges work on sorted, disjoint lists of these and
+//! convert back at the end.
+//!
+//! `0.0.0` is treated as the bottom of the version space, so the complement
+//! of `<1` is `>=1` rather than something that also covers `0.0.0-alpha`.
+
+use std::{cmp::Ordering, ops::Bound};
+
+use super::{Constraint, Range};
+use crate::semver::Semver;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Interval {
+    pub lo: Bound<Semver>,
+    pub hi: Bound<Semver>,
+}
+
+fn zero() -> Semver {
+    Semver {
+        components: vec![0, 0, 0],
+        raw: "0.0.0".to_string(),
+        ..Default::default()
+    }
+}
+
+// `Included(v)` starts before `Excluded(v)`.
+fn cmp_lo(a: &Bound<Semver>, b: &Bound<Semver>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(a), Bound::Excluded(b)) if a == b => Ordering::Less,
+        (Bound::Excluded(a), Bound::Included(b)) if a == b => Ordering::Greater,
+        (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b)) => {
+            a.cmp(b)
+        }
+    }
+}
+
+// `Excluded(v)` ends before `Included(v)`.
+fn cmp_hi(a: &Bound<Semver>, b: &Bound<Semver>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Greater,
+        (_, Bound::Unbounded) => Ordering::Less,
+        (Bound::Included(a), Bound::Excluded(b)) if a == b => Ordering::Greater,
+        (Bound::Excluded(a), Bound::Included(b)) if a == b => Ordering::Less,
+        (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b)) => {
+            a.cmp(b)
+        }
+    }
+}
+
+// The bound on the other side of the same point: what's left of `[v` ends
+// at `v)`, and what's right of `v]` starts at `(v`.
+fn flip(b: &Bound<Semver>) -> Bound<Semver> {
+    match b {
+        Bound::Included(v) => Bound::Excluded(v.clone()),
+        Bound::Excluded(v) => Bound::Included(v.clone()),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+impl Interval {
+    pub fn everything() -> Self {
+        Self {
+            lo: Bound::Included(zero()),
+            hi: Bound::Unbounded,
+        }
+    }
+
+    /// The versions `c` matches, or for an exclusion, the one it removes.
+    pub fn from_constraint(c: &Constraint) -> Self {
+        match c {
+            Constraint::Any => Self::everything(),
+            Constraint::Single(v) | Constraint::Exclude(v) => Self {
+                lo: Bound::Included(v.clone()),
+                hi: Bound::Included(v.clone()),
+            },
+            Constraint::Contiguous(Bound::Unbounded, hi) => Self {
+                lo: Bound::Included(zero()),
+                hi: hi.clone(),
+            },
+            Constraint::Contiguous(lo, hi) => Self {
+                lo: lo.clone(),
+                hi: hi.clone(),
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let (lo, lo_inclusive) = match &self.lo {
+            Bound::Included(v) => (v, true),
+            Bound::Excluded(v) => (v, false),
+            Bound::Unbounded => return false,
+        };
+        match &self.hi {
+            Bound::Unbounded => false,
+            Bound::Included(hi) if lo_inclusive => lo.gt(hi),
+            Bound::Included(hi) | Bound::Excluded(hi) => !lo.lt(hi),
+        }
+    }
+
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let lo = if cmp_lo(&self.lo, &other.lo).is_ge() {
+            &self.lo
+        } else {
+            &other.lo
+        };
+        let hi = if cmp_hi(&self.hi, &other.hi).is_le() {
+            &self.hi
+        } else {
+            &other.hi
+        };
+        let rv = Self {
+            lo: lo.clone(),
+            hi: hi.clone(),
+        };
+        (!rv.is_empty()).then_some(rv)
+    }
+
+    // Whether `next`, which starts no earlier than `self`, overlaps or abuts
+    // it, so that the two can be merged into one interval.
+    fn touches(&self, next: &Self) -> bool {
+        match (&self.hi, &next.lo) {
+            (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+            (Bound::Excluded(hi), Bound::Excluded(lo)) => lo.lt(hi),
+            (
+                Bound::Included(hi) | Bound::Excluded(hi),
+                Bound::Included(lo) | Bound::Excluded(lo),
+            ) => !lo.gt(hi),
+        }
+    }
+}
+
+/// Sorts and merges intervals into the canonical, disjoint form every other
+/// operation here expects.
+pub(crate) fn union(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|i| !i.is_empty());
+    intervals.sort_by(|a, b| cmp_lo(&a.lo, &b.lo));
+
+    let mut rv: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for next in intervals {
+        match rv.last_mut() {
+            Some(last) if last.touches(&next) => {
+                if cmp_hi(&next.hi, &last.hi).is_gt() {
+                    last.hi = next.hi;
+                }
+            }
+            _ => rv.push(next),
+        }
+    }
+    rv
+}
+
+pub(crate) fn intersect(a: &[Interval], b: &[Interval]) -> Vec<Interval> {
+    union(
+        a.iter()
+            .flat_map(|aa| b.iter().filter_map(|bb| aa.intersect(bb)))
+            .collect(),
+    )
+}
+
+/// Everything in `[0, ∞)` not covered by `intervals`, which must be
+/// canonical (see [`union`]).
+pub(crate) fn complement(intervals: &[Interval]) -> Vec<Interval> {
+    let mut rv = Vec::new();
+    let mut lo = Bound::Included(zero());
+    for i in intervals {
+        let gap = Interval {
+            lo,
+            hi: flip(&i.lo),
+        };
+        if !gap.is_empty() {
+            rv.push(gap);
+        }
+        if i.hi == Bound::Unbounded {
+            return rv;
+        }
+        lo = flip(&i.hi);
+    }
+    rv.push(Interval {
+        lo,
+        hi: Bound::Unbounded,
+    });
+    rv
+}
+
+impl Range {
+    pub(crate) fn intervals(&self) -> Vec<Interval> {
+        let (exclusions, constraints): (Vec<_>, Vec<_>) = self
+            .set
+            .iter()
+            .partition(|c| matches!(c, Constraint::Exclude(_)));
+        // A range of only exclusions starts from `*`.
+        let rv = if constraints.is_empty() && !exclusions.is_empty() {
+            vec![Interval::everything()]
+        } else {
+            union(
+                constraints
+                    .into_iter()
+                    .map(Interval::from_constraint)
+                    .collect(),
+            )
+        };
+        let excluded = union(
+            exclusions
+                .into_iter()
+                .map(Interval::from_constraint)
+                .collect(),
+        );
+        intersect(&rv, &complement(&excluded))
+    }
+
+    /// Builds a range from canonical intervals. A version missing from
+    /// between two intervals is written as one exclusion rather than two
+    /// constraints, so that `^1.4,!=1.4.7` comes back out as it went in.
+    pub(crate) fn from_intervals(intervals: Vec<Interval>) -> Self {
+        let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+        let mut exclusions = Vec::new();
+        for i in intervals {
+            match (merged.last_mut(), &i.lo) {
+                (Some(last), Bound::Excluded(v)) if last.hi == Bound::Excluded(v.clone()) => {
+                    exclusions.push(v.clone());
+                    last.hi = i.hi;
+                }
+                _ => merged.push(i),
+            }
+        }
+
+        let mut set = Vec::with_capacity(merged.len() + exclusions.len());
+        for i in merged {
+            set.push(match (i.lo, i.hi) {
+                (Bound::Included(lo), Bound::Unbounded) if lo == zero() => Constraint::Any,
+                (Bound::Included(lo), Bound::Included(hi)) if lo == hi => Constraint::Single(lo),
+                (lo, hi) => Constraint::Contiguous(lo, hi),
+            });
+        }
+        // Exclusions alone already start from `*`, which couldn't be written
+        // beside them.
+        if set == [Constraint::Any] && !exclusions.is_empty() {
+            set.clear();
+        }
+        set.extend(exclusions.into_iter().map(Constraint::Exclude));
+        let mut rv = Range {
+            raw: "".to_string(),
+            set,
+        };
+        rv.raw = rv.raw();
+        rv
+    }
+}
@@ -1,24 +1,26 @@
+use std::ops::Bound;
+
 use crate::semver::Semver;
 
-use super::{Constraint, Range};
+use super::{satisfies::SatisfyOptions, Constraint, Range};
 
 impl Range {
-    pub fn max(&self, semvers: &[Semver]) -> Option<Semver> {
-        let rv = semvers.iter().filter(|s| self.satisfies(s)).fold(
-            Semver::parse("0").unwrap(),
-            |max, s| {
-                if max.gt(s) {
-                    max
-                } else {
-                    s.clone()
-                }
-            },
-        );
-        let zero = Semver::parse("0").unwrap();
-        if rv.eq(&zero) {
-            return None;
-        }
-        Some(Semver::parse(&rv.raw).unwrap())
+    /// The greatest version that satisfies the range, if any does.
+    pub fn max<'a>(&self, semvers: impl IntoIterator<Item = &'a Semver>) -> Option<Semver> {
+        self.max_with(semvers, &SatisfyOptions::default())
+    }
+
+    /// [`Range::max`], with `options` deciding which prereleases match.
+    pub fn max_with<'a>(
+        &self,
+        semvers: impl IntoIterator<Item = &'a Semver>,
+        options: &SatisfyOptions,
+    ) -> Option<Semver> {
+        semvers
+            .into_iter()
+            .filter(|s| self.satisfies_with(s, options))
+            .max()
+            .cloned()
     }
 }
 
@@ -27,7 +29,20 @@ impl Constraint {
         match self {
             Constraint::Any => true,
             Constraint::Single(v) => v.eq(semver),
-            Constraint::Contiguous(v1, v2) => v1.eq(semver) || (v1.lt(semver) && v2.gt(semver)),
+            Constraint::Contiguous(lo, hi) => {
+                let above = match lo {
+                    Bound::Included(v) => !v.gt(semver),
+                    Bound::Excluded(v) => v.lt(semver),
+                    Bound::Unbounded => true,
+                };
+                let below = match hi {
+                    Bound::Included(v) => !v.lt(semver),
+                    Bound::Excluded(v) => v.gt(semver),
+                    Bound::Unbounded => true,
+                };
+                above && below
+            }
+            Constraint::Exclude(v) => v.neq(semver),
         }
     }
 }
@@ -0,0 +1,23 @@
+use crate::semver::Semver;
+
+use super::{satisfies::SatisfyOptions, Range};
+
+impl Range {
+    /// The least version that satisfies the range, if any does.
+    pub fn min<'a>(&self, semvers: impl IntoIterator<Item = &'a Semver>) -> Option<Semver> {
+        self.min_with(semvers, &SatisfyOptions::default())
+    }
+
+    /// [`Range::min`], with `options` deciding which prereleases match.
+    pub fn min_with<'a>(
+        &self,
+        semvers: impl IntoIterator<Item = &'a Semver>,
+        options: &SatisfyOptions,
+    ) -> Option<Semver> {
+        semvers
+            .into_iter()
+            .filter(|s| self.satisfies_with(s, options))
+            .min()
+            .cloned()
+    }
+}
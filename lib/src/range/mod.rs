@@ -1,15 +1,25 @@
 use crate::semver::Semver;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::{
-    fmt,
-    hash::{Hash, Hasher},
-};
+use std::{fmt, ops::Bound};
 
+pub mod complement;
+pub mod dialect;
+pub mod difference;
+pub mod disjoint;
+pub mod filter;
 pub mod intersect;
+mod interval;
 pub mod max;
+pub mod min;
+pub mod normalize;
 pub mod parse;
 pub mod satisfies;
+pub mod subset;
+pub mod union;
+
+// How the empty range is written: nothing is below `0`.
+const EMPTY: &str = "<0";
 
 #[derive(Debug, Clone)]
 pub struct Range {
@@ -21,7 +31,14 @@ pub struct Range {
 pub enum Constraint {
     Any,
     Single(Semver),
-    Contiguous(Semver, Semver),
+    /// The versions between two bounds, eg. `>=1<2` or `>1.2<=1.4`. An
+    /// unbounded upper end reaches to infinity; the lower end is never
+    /// below `0`, so `Unbounded` there means `>=0`.
+    Contiguous(Bound<Semver>, Bound<Semver>),
+    /// Every version but this one, eg. `!=1.4.7`. Exclusions remove versions
+    /// from the union of a range's other constraints, or from `*` if it has
+    /// no others.
+    Exclude(Semver),
 }
 
 impl Eq for Constraint {}
@@ -31,37 +48,48 @@ impl Constraint {
         match self {
             Constraint::Any => "*".to_string(),
             Constraint::Single(v) => format!("={}", v.raw),
-            Constraint::Contiguous(v1, v2) => format!(">={}<{}", v1.raw, v2.raw),
+            Constraint::Contiguous(lo, hi) => {
+                format!(
+                    "{}{}",
+                    lower(lo, |v| v.raw.clone()),
+                    upper(hi, |v| v.raw.clone())
+                )
+            }
+            Constraint::Exclude(v) => format!("!={}", v.raw),
         }
     }
 }
 
 impl Range {
+    /// Whether no version satisfies this range. See [`Range::none`].
+    pub fn is_empty(&self) -> bool {
+        self.intervals().is_empty()
+    }
+
     pub fn raw(&self) -> String {
+        if self.set.is_empty() {
+            return EMPTY.to_string();
+        }
         let rv = self.set.iter().map(|c| c.raw()).collect::<Vec<String>>();
         rv.join(",")
     }
 }
 
-impl Hash for Semver {
-    #[cfg(not(tarpaulin_include))]
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.components.hash(state);
-    }
-}
-
 impl fmt::Display for Range {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.set.is_empty() {
+            return write!(f, "{EMPTY}");
+        }
         let str = self
             .set
             .iter()
             .map(|v| match v {
                 Constraint::Any => "*".to_string(),
                 Constraint::Single(v) => format!("={v}"),
-                Constraint::Contiguous(v1, v2) => {
-                    let v1_chomp = v1.raw.trim_end_matches(".0").to_string();
-                    let v2_chomp = v2.raw.trim_end_matches(".0").to_string();
-                    if v2.major == v1.major + 1 && v2.minor == 0 && v2.patch == 0 {
+                Constraint::Exclude(v) => format!("!={v}"),
+                Constraint::Contiguous(Bound::Included(v1), Bound::Excluded(v2)) => {
+                    let v1_chomp = chomp(v1);
+                    if v1.major.checked_add(1) == Some(v2.major) && v2.minor == 0 && v2.patch == 0 {
                         if v1.major == 0 {
                             if v1.components.len() == 1 {
                                 "^0".to_string()
@@ -71,16 +99,20 @@ impl fmt::Display for Range {
                         } else {
                             format!("^{v1_chomp}")
                         }
-                    } else if v2.major == v1.major && v2.minor == v1.minor + 1 && v2.patch == 0 {
+                    } else if v2.major == v1.major
+                        && v1.minor.checked_add(1) == Some(v2.minor)
+                        && v2.patch == 0
+                    {
                         format!("~{v1_chomp}")
-                    } else if v2.major == usize::MAX {
-                        format!(">={v1_chomp}")
-                    } else if at(&v1.clone(), &v2.clone()) {
+                    } else if at(v1, v2) {
                         format!("@{v1}")
                     } else {
-                        format!(">={v1_chomp}<{v2_chomp}")
+                        format!(">={v1_chomp}<{}", chomp(v2))
                     }
                 }
+                Constraint::Contiguous(lo, hi) => {
+                    format!("{}{}", lower(lo, chomp), upper(hi, chomp))
+                }
             })
             .collect::<Vec<_>>()
             .join(",");
@@ -88,6 +120,32 @@ impl fmt::Display for Range {
     }
 }
 
+// `v` without trailing zero components, eg. `1.2` for `1.2.0`. Zeros in
+// the prerelease or build count, so they stay.
+fn chomp(v: &Semver) -> String {
+    let end = v.raw.find(['-', '+']).unwrap_or(v.raw.len());
+    let (release, rest) = v.raw.split_at(end);
+    format!("{}{rest}", release.trim_end_matches(".0"))
+}
+
+// `>=v` or `>v`, with `v` written by `write`.
+fn lower(b: &Bound<Semver>, write: impl Fn(&Semver) -> String) -> String {
+    match b {
+        Bound::Included(v) => format!(">={}", write(v)),
+        Bound::Excluded(v) => format!(">{}", write(v)),
+        Bound::Unbounded => ">=0".to_string(),
+    }
+}
+
+// `<v`, `<=v`, or nothing for no upper bound.
+fn upper(b: &Bound<Semver>, write: impl Fn(&Semver) -> String) -> String {
+    match b {
+        Bound::Included(v) => format!("<={}", write(v)),
+        Bound::Excluded(v) => format!("<{}", write(v)),
+        Bound::Unbounded => "".to_string(),
+    }
+}
+
 /// checks @ syntax, eg. node@22.1
 /// `@` is `=`, as long as there's 3 components
 fn at(left: &Semver, right: &Semver) -> bool {
@@ -108,7 +166,7 @@ fn at(left: &Semver, right: &Semver) -> bool {
         cc1.push(0);
     }
 
-    if last(&cc1) + 1 != last(cc2) {
+    if last(&cc1).checked_add(1) != Some(last(cc2)) {
         return false;
     }
 
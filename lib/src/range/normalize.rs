@@ -0,0 +1,16 @@
+use super::Range;
+
+impl Range {
+    /// An equivalent range with constraints sorted, overlapping or adjacent
+    /// intervals merged, `=` constraints inside an interval absorbed, and
+    /// anything covering every version collapsed to `*`.
+    pub fn normalize(&self) -> Range {
+        Range::from_intervals(self.intervals())
+    }
+
+    /// Whether both ranges are satisfied by exactly the same versions,
+    /// however they're written. `==` compares constraints one by one.
+    pub fn equivalent(&self, other: &Range) -> bool {
+        self.intervals() == other.intervals()
+    }
+}
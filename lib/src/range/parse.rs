@@ -1,15 +1,17 @@
+use std::ops::Bound;
+
 use crate::semver::Semver;
 
-use super::{Constraint, Range};
-use crate::error::{Error, Result};
+use super::{interval::Interval, Constraint, Range, EMPTY};
+use crate::error::{Diagnostic, Error, ErrorKind, Part, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
 
 lazy_static! {
     static ref RANGE_REGEX: Regex = Regex::new(r"\s*(,|\|\|)\s*").unwrap();
     static ref CONSTRAINT_REGEX_RANGE: Regex =
-        Regex::new(r"^>=((\d+\.)*\d+)\s*(<((\d+\.)*\d+))?$").unwrap();
-    static ref CONSTRAINT_REGEX_SIMPLE: Regex = Regex::new(r"^([~=<^@])(.+)$").unwrap();
+        Regex::new(r"^(>=?)((\d+\.)*\d+)\s*((<=?)((\d+\.)*\d+))?$").unwrap();
+    static ref CONSTRAINT_REGEX_SIMPLE: Regex = Regex::new(r"^(!=|<=|[~=<^@])(.+)$").unwrap();
     static ref INFINITIES_REGEX: Regex = Regex::new(r"<Infinity(\.Infinity)+").unwrap();
 }
 
@@ -24,32 +26,47 @@ impl Range {
         let raw = range.to_string();
         let mut set = Vec::new();
 
-        if range.is_empty() {
-            return Err(Error::Range("no constraints".into()));
+        if range.is_empty() || range == EMPTY {
+            return Ok(Self::none());
         }
 
         if range == "*" {
             set.push(Constraint::Any);
             return Ok(Self { raw, set });
         }
-        set = RANGE_REGEX
-            .split(range)
-            .map(Constraint::parse)
-            .collect::<Result<Vec<Constraint>>>()?;
-
-        for c in set.iter() {
-            if let Constraint::Contiguous(v1, v2) = c {
-                if !v1.lt(v2) {
-                    return Err(Error::Range(format!(
-                        "{} is greater than {}",
-                        v1.raw, v2.raw
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        for sep in RANGE_REGEX.find_iter(range) {
+            pieces.push((start, &range[start..sep.start()]));
+            start = sep.end();
+        }
+        pieces.push((start, &range[start..]));
+
+        for (start, piece) in pieces {
+            let c = Constraint::parse(piece).map_err(|e| e.within(range, start))?;
+            if let Constraint::Contiguous(..) = &c {
+                if Interval::from_constraint(&c).is_empty() {
+                    return Err(Error::Parse(Diagnostic::new(
+                        ErrorKind::InvertedBounds,
+                        start..start + piece.len(),
+                        range,
                     )));
                 }
             }
+            set.push(c);
         }
         Ok(Self { raw, set })
     }
 
+    /// The range no version satisfies, eg. the intersection of `~1.2` and
+    /// `~1.3`. Written `<0`; parsing `""` also produces it.
+    pub fn none() -> Self {
+        Self {
+            raw: EMPTY.to_string(),
+            set: vec![],
+        }
+    }
+
     pub fn any() -> Self {
         Self {
             raw: "*".to_string(),
@@ -66,8 +83,8 @@ impl Range {
     pub fn contiguous(v1: &str, v2: &str) -> Result<Self> {
         let raw = format!(">={v1}<{v2}");
         let set = vec![Constraint::Contiguous(
-            Semver::parse(v1)?,
-            Semver::parse(v2)?,
+            Bound::Included(Semver::parse(v1)?),
+            Bound::Excluded(Semver::parse(v2)?),
         )];
         Ok(Self { raw, set })
     }
@@ -91,30 +108,35 @@ impl Range {
 
 impl Constraint {
     pub fn parse(constraint: &str) -> Result<Self> {
+        if let Some(e) = bad_operator(constraint) {
+            return Err(e);
+        }
+
         if let Some(cap) = CONSTRAINT_REGEX_RANGE.captures(constraint) {
-            let v1 = Semver::parse(
-                cap.get(1)
-                    .ok_or_else(|| Error::Range("invalid description".into()))?
-                    .as_str(),
+            let v1 = version(
+                constraint,
+                cap.get(2)
+                    .ok_or_else(|| Error::Range("invalid description".into()))?,
             )?;
-            let v2 = if cap.get(3).is_some() {
-                Semver::parse(
-                    cap.get(4)
-                        .ok_or_else(|| Error::Range("invalid description".into()))?
-                        .as_str(),
-                )?
+            let lo = if &cap[1] == ">" {
+                Bound::Excluded(v1)
             } else {
-                Semver::infinty()
+                Bound::Included(v1)
             };
-            return Ok(Constraint::Contiguous(v1, v2));
+            let hi = match cap.get(6) {
+                Some(m) if &cap[5] == "<=" => Bound::Included(version(constraint, m)?),
+                Some(m) => Bound::Excluded(version(constraint, m)?),
+                None => Bound::Unbounded,
+            };
+            return Ok(Constraint::Contiguous(lo, hi));
         }
 
         // ^0 is a special case, in that it doesn't work like
         // ^0.x or ^0.x.y, but rather like any other ^x
         if constraint == "^0" {
             return Ok(Constraint::Contiguous(
-                Semver::parse("0.0.0")?,
-                Semver::parse("1.0.0")?,
+                Bound::Included(Semver::parse("0.0.0")?),
+                Bound::Excluded(Semver::parse("1.0.0")?),
             ));
         }
 
@@ -125,55 +147,81 @@ impl Constraint {
                 .as_str()
             {
                 "^" => {
-                    let v1 = Semver::parse(
+                    let v1 = version(
+                        constraint,
                         cap.get(2)
-                            .ok_or_else(|| Error::Range("invalid description".into()))?
-                            .as_str(),
+                            .ok_or_else(|| Error::Range("invalid description".into()))?,
                     )?;
                     if v1.major > 0 {
-                        let v2 = Semver::parse(&format!("{}", v1.major + 1))?;
-                        return Ok(Constraint::Contiguous(v1, v2));
+                        let v2 = Semver::parse(&format!("{}", succ(v1.major, &cap, constraint)?))?;
+                        return Ok(Constraint::Contiguous(
+                            Bound::Included(v1),
+                            Bound::Excluded(v2),
+                        ));
                     } else if v1.minor > 0 {
-                        let v2 = Semver::parse(&format!("{}.{}", v1.major, v1.minor + 1))?;
-                        return Ok(Constraint::Contiguous(v1, v2));
+                        let minor = succ(v1.minor, &cap, constraint)?;
+                        let v2 = Semver::parse(&format!("{}.{minor}", v1.major))?;
+                        return Ok(Constraint::Contiguous(
+                            Bound::Included(v1),
+                            Bound::Excluded(v2),
+                        ));
                     } else {
                         return Ok(Constraint::Single(v1));
                     }
                 }
                 "~" => {
-                    let v1 = Semver::parse(
+                    let v1 = version(
+                        constraint,
                         cap.get(2)
-                            .ok_or_else(|| Error::Range("invalid description".into()))?
-                            .as_str(),
+                            .ok_or_else(|| Error::Range("invalid description".into()))?,
                     )?;
 
                     let v2 = if v1.components.len() == 1 {
-                        Semver::parse(&format!("{}", v1.major + 1))?
+                        Semver::parse(&format!("{}", succ(v1.major, &cap, constraint)?))?
                     } else {
-                        Semver::parse(&format!("{}.{}", v1.major, v1.minor + 1))?
+                        let minor = succ(v1.minor, &cap, constraint)?;
+                        Semver::parse(&format!("{}.{minor}", v1.major))?
                     };
-                    Ok(Constraint::Contiguous(v1, v2))
+                    Ok(Constraint::Contiguous(
+                        Bound::Included(v1),
+                        Bound::Excluded(v2),
+                    ))
                 }
                 "<" => {
                     let v1 = Semver::parse("0")?;
-                    let v2 = Semver::parse(
+                    let v2 = version(
+                        constraint,
+                        cap.get(2)
+                            .ok_or_else(|| Error::Range("invalid description".into()))?,
+                    )?;
+                    Ok(Constraint::Contiguous(
+                        Bound::Included(v1),
+                        Bound::Excluded(v2),
+                    ))
+                }
+                "<=" => {
+                    let v1 = Semver::parse("0")?;
+                    let v2 = version(
+                        constraint,
                         cap.get(2)
-                            .ok_or_else(|| Error::Range("invalid description".into()))?
-                            .as_str(),
+                            .ok_or_else(|| Error::Range("invalid description".into()))?,
                     )?;
-                    Ok(Constraint::Contiguous(v1, v2))
+                    Ok(Constraint::Contiguous(
+                        Bound::Included(v1),
+                        Bound::Included(v2),
+                    ))
                 }
                 "@" => {
-                    let v1 = Semver::parse(
+                    let v1 = version(
+                        constraint,
                         cap.get(2)
-                            .ok_or_else(|| Error::Range("invalid description".into()))?
-                            .as_str(),
+                            .ok_or_else(|| Error::Range("invalid description".into()))?,
                     )?;
                     let mut parts = v1.components.clone();
                     let last = parts
                         .last_mut()
                         .ok_or_else(|| Error::Range("version too short".into()))?;
-                    *last += 1;
+                    *last = succ(*last, &cap, constraint)?;
                     let v2 = Semver::parse(
                         &parts
                             .iter()
@@ -181,19 +229,101 @@ impl Constraint {
                             .collect::<Vec<_>>()
                             .join("."),
                     )?;
-                    Ok(Constraint::Contiguous(v1, v2))
+                    Ok(Constraint::Contiguous(
+                        Bound::Included(v1),
+                        Bound::Excluded(v2),
+                    ))
                 }
-                "=" => Ok(Constraint::Single(Semver::parse(
+                "=" => Ok(Constraint::Single(version(
+                    constraint,
+                    cap.get(2)
+                        .ok_or_else(|| Error::Range("invalid description".into()))?,
+                )?)),
+                "!=" => Ok(Constraint::Exclude(version(
+                    constraint,
                     cap.get(2)
-                        .ok_or_else(|| Error::Range("invalid description".into()))?
-                        .as_str(),
+                        .ok_or_else(|| Error::Range("invalid description".into()))?,
                 )?)),
                 _ => unreachable!("invalid range description: {}", constraint),
             };
         }
-        Err(Error::Range(format!(
-            "invalid range description: {}",
-            constraint
-        )))
+        Err(locate(constraint))
     }
 }
+
+// Parses the version captured by `m`, pointing any diagnostic at its place in
+// the whole constraint.
+fn version(constraint: &str, m: regex::Match) -> Result<Semver> {
+    Semver::parse(m.as_str()).map_err(|e| e.within(constraint, m.start()))
+}
+
+// `n + 1`, for the bound after the version captured in `cap`, or an
+// overflow diagnostic pointing at it.
+fn succ(n: usize, cap: &regex::Captures, constraint: &str) -> Result<usize> {
+    n.checked_add(1).ok_or_else(|| {
+        let span = cap.get(2).map_or(0..constraint.len(), |m| m.range());
+        Error::Parse(Diagnostic::new(
+            ErrorKind::Overflow(Part::Component),
+            span,
+            constraint,
+        ))
+    })
+}
+
+// Rejects constraints that don't start with one of the grammar's operators,
+// eg. `=>1`, `~>1` or a bare `1.2`.
+fn bad_operator(constraint: &str) -> Option<Error> {
+    if constraint.is_empty() {
+        return Some(Error::Parse(Diagnostic::new(
+            ErrorKind::EmptyConstraint,
+            0..0,
+            constraint,
+        )));
+    }
+
+    let op_len = constraint
+        .find(|c: char| !"<>=!~^@*".contains(c))
+        .unwrap_or(constraint.len());
+    let op = &constraint[..op_len];
+    if matches!(op, ">=" | ">" | "~" | "=" | "<" | "<=" | "^" | "@" | "!=") {
+        return None;
+    }
+    let end = if op.is_empty() {
+        constraint.chars().next().map_or(0, char::len_utf8)
+    } else {
+        op_len
+    };
+    Some(Error::Parse(Diagnostic::new(
+        ErrorKind::BadOperator(op.to_string()),
+        0..end,
+        constraint,
+    )))
+}
+
+// Explains why a constraint with a valid operator matched none of the
+// grammar's forms: either the version is missing, or it's `>=a<b` (or `>a`,
+// `<=b`, ...), whose bounds are digits only.
+fn locate(constraint: &str) -> Error {
+    let mut start = constraint
+        .find(|c: char| !"<>=!~^@".contains(c))
+        .unwrap_or(constraint.len());
+    for bound in constraint[start..].split('<') {
+        // The `=` of an upper `<=`.
+        let at = start + bound.len() - bound.trim_start_matches('=').len();
+        let trimmed = constraint[at..start + bound.len()].trim_end();
+        if let Err(e) = Semver::parse(trimmed) {
+            return e.within(constraint, at);
+        }
+        if let Some(i) = trimmed.find(|c: char| !c.is_ascii_digit() && c != '.') {
+            let c = trimmed[i..].chars().next().unwrap_or_default();
+            return Error::Parse(Diagnostic::new(
+                ErrorKind::UnknownCharacter(c),
+                at + i..at + i + c.len_utf8(),
+                constraint,
+            ));
+        }
+        start += bound.len() + 1;
+    }
+
+    Error::Range(format!("invalid range description: {}", constraint))
+}
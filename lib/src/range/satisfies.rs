@@ -1,9 +1,92 @@
-use crate::semver::Semver;
+use std::ops::Bound;
 
-use super::Range;
+use crate::{
+    error::{Error, Result},
+    semver::Semver,
+};
+
+use super::{Constraint, Range};
+
+/// Which prereleases a range matches, on top of sorting inside it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PrereleasePolicy {
+    /// No prerelease satisfies any range.
+    Never,
+    /// A prerelease only satisfies a constraint that names a prerelease of
+    /// the same `MAJOR.MINOR.PATCH`, like npm and Cargo do: `^1.2.3-beta`
+    /// matches `1.2.3-rc` but not `1.3.0-beta`.
+    SameTuple,
+    /// Prereleases satisfy every range they sort inside. This is pkgx's
+    /// behavior.
+    #[default]
+    Always,
+}
+
+impl PrereleasePolicy {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "never" => Ok(Self::Never),
+            "same-tuple" => Ok(Self::SameTuple),
+            "always" => Ok(Self::Always),
+            _ => Err(Error::Range(format!("invalid prerelease policy '{}'", s))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SatisfyOptions {
+    pub include_prerelease: PrereleasePolicy,
+}
 
 impl Range {
     pub fn satisfies(&self, semver: &Semver) -> bool {
-        self.set.iter().any(|c| c.satisfies(semver))
+        self.satisfies_with(semver, &SatisfyOptions::default())
+    }
+
+    /// [`Range::satisfies`], with `options` deciding which prereleases match.
+    pub fn satisfies_with(&self, semver: &Semver, options: &SatisfyOptions) -> bool {
+        let (exclusions, constraints): (Vec<_>, Vec<_>) = self
+            .set
+            .iter()
+            .partition(|c| matches!(c, Constraint::Exclude(_)));
+        let allowed = |c: &Constraint| {
+            if semver.prerelease.is_empty() {
+                return true;
+            }
+            match options.include_prerelease {
+                PrereleasePolicy::Never => false,
+                PrereleasePolicy::SameTuple => c.names_prerelease_of(semver),
+                PrereleasePolicy::Always => true,
+            }
+        };
+        // A range of only exclusions starts from `*`.
+        let included = if constraints.is_empty() {
+            !exclusions.is_empty() && allowed(&Constraint::Any)
+        } else {
+            constraints
+                .iter()
+                .any(|c| c.satisfies(semver) && allowed(c))
+        };
+        included && exclusions.iter().all(|c| c.satisfies(semver))
+    }
+}
+
+impl Constraint {
+    // Whether one of the constraint's versions is a prerelease of the same
+    // `MAJOR.MINOR.PATCH` as `semver`.
+    fn names_prerelease_of(&self, semver: &Semver) -> bool {
+        let bound = |b: &Bound<Semver>| match b {
+            Bound::Included(v) | Bound::Excluded(v) => Some(v.clone()),
+            Bound::Unbounded => None,
+        };
+        let versions = match self {
+            Constraint::Any => vec![],
+            Constraint::Single(v) | Constraint::Exclude(v) => vec![v.clone()],
+            Constraint::Contiguous(lo, hi) => bound(lo).into_iter().chain(bound(hi)).collect(),
+        };
+        versions.iter().any(|v| {
+            !v.prerelease.is_empty()
+                && (v.major, v.minor, v.patch) == (semver.major, semver.minor, semver.patch)
+        })
     }
 }
@@ -0,0 +1,14 @@
+use super::{interval, Range};
+
+impl Range {
+    /// Whether every version satisfying `self` also satisfies `range`.
+    pub fn is_subset_of(&self, range: &Range) -> bool {
+        let outside = interval::complement(&range.intervals());
+        interval::intersect(&self.intervals(), &outside).is_empty()
+    }
+
+    /// Whether every version satisfying `range` also satisfies `self`.
+    pub fn is_superset_of(&self, range: &Range) -> bool {
+        range.is_subset_of(self)
+    }
+}
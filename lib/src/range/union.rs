@@ -0,0 +1,11 @@
+use super::{interval, Range};
+
+impl Range {
+    /// Versions satisfying either range, merged into sorted, non-overlapping
+    /// constraints.
+    pub fn union(&self, range: &Range) -> Range {
+        let mut intervals = self.intervals();
+        intervals.extend(range.intervals());
+        Range::from_intervals(interval::union(intervals))
+    }
+}
@@ -1,16 +1,148 @@
 use crate::error::{Error, Result};
 
-use super::Semver;
+use super::{prerelease::Identifier, Semver};
 
 impl Semver {
     pub fn bump(&self, which: &SemverComponent) -> Result<Self> {
+        self.bump_with(which, None)
+    }
+
+    /// [`Semver::bump`], naming the prereleases the `Pre*` bumps start with
+    /// `preid`, eg. `rc` for `2.0.0-rc.0`. Without one they are just a
+    /// number, `2.0.0-0`.
+    pub fn bump_with(&self, which: &SemverComponent, preid: Option<&str>) -> Result<Self> {
+        let (numbers, letter) = self.shape();
         match which {
-            SemverComponent::Major => Self::from((self.major + 1, 0, 0)),
-            SemverComponent::Minor => Self::from((self.major, self.minor + 1, 0)),
-            SemverComponent::Patch => Self::from((self.major, self.minor, self.patch + 1)),
+            SemverComponent::Major => self.bump_index(0),
+            SemverComponent::Minor => self.bump_index(1),
+            SemverComponent::Patch => self.bump_index(2),
+            SemverComponent::Index(i) => self.bump_index(*i),
+            SemverComponent::Letter => self.bump_letter(),
+            SemverComponent::Premajor => self.bump_index(0)?.with(&first_prerelease(preid)),
+            SemverComponent::Preminor => self.bump_index(1)?.with(&first_prerelease(preid)),
+            SemverComponent::Prepatch => self.bump_index(2)?.with(&first_prerelease(preid)),
+            SemverComponent::Prerelease if self.prerelease.is_empty() => {
+                self.bump_with(&SemverComponent::Prepatch, preid)
+            }
+            SemverComponent::Prerelease => {
+                let pre = match preid {
+                    // Switching to another preid starts its count over.
+                    Some(id) if self.prerelease.first() != Some(&Identifier::parse(id)) => {
+                        first_prerelease(preid)
+                    }
+                    _ => next_prerelease(&self.prerelease)?,
+                };
+                render(&numbers, letter, &pre)
+            }
+            SemverComponent::Release => render(&numbers, letter, &[]),
             SemverComponent::None => Ok(self.clone()),
         }
     }
+
+    // The numeric components and the letter suffix, if any: `1.1.1q` is
+    // `[1, 1, 1]` and `q`.
+    fn shape(&self) -> (Vec<usize>, Option<char>) {
+        let release = self.raw.split(['-', '+']).next().unwrap_or_default();
+        match release.chars().last().filter(char::is_ascii_lowercase) {
+            Some(letter) => (
+                self.components[..self.components.len() - 1].to_vec(),
+                Some(letter),
+            ),
+            None => (self.components.clone(), None),
+        }
+    }
+
+    // Counts up component `i`, zeroing the ones after it and dropping any
+    // letter. Missing components up to the patch are filled in with zeros,
+    // so the patch of `1.2` bumps to `1.2.1`, and one more can be added, so
+    // component 3 of `1.2.3` bumps to `1.2.3.1`. On `1.1.1q` the letter is
+    // component 3.
+    fn bump_index(&self, i: usize) -> Result<Self> {
+        let (mut numbers, letter) = self.shape();
+        let missing = || Error::Bump(format!("{} has no component {i} to bump", self.raw));
+        if letter.is_some() && i >= numbers.len() {
+            if i == numbers.len() {
+                return self.bump_letter();
+            }
+            return Err(missing());
+        }
+        if i > numbers.len().max(3) {
+            return Err(missing());
+        }
+
+        if numbers.len() <= i {
+            numbers.resize(i.checked_add(1).ok_or_else(missing)?, 0);
+        }
+        numbers[i] = numbers[i]
+            .checked_add(1)
+            .ok_or_else(|| Error::Bump(format!("component {i} of {} overflows", self.raw)))?;
+        for n in &mut numbers[i + 1..] {
+            *n = 0;
+        }
+        render(&numbers, None, &[])
+    }
+
+    // `1.1.1q` to `1.1.1r`, and `1.1.1` to `1.1.1a`.
+    fn bump_letter(&self) -> Result<Self> {
+        let (numbers, letter) = self.shape();
+        let next = match letter {
+            None => 'a',
+            Some('z') => {
+                return Err(Error::Bump(format!(
+                    "the letter of {} is already z",
+                    self.raw
+                )))
+            }
+            Some(c) => (c as u8 + 1) as char,
+        };
+        render(&numbers, Some(next), &[])
+    }
+
+    // Replaces the prerelease.
+    fn with(&self, pre: &[Identifier]) -> Result<Self> {
+        let (numbers, letter) = self.shape();
+        render(&numbers, letter, pre)
+    }
+}
+
+// `preid.0`, or just `0`.
+fn first_prerelease(preid: Option<&str>) -> Vec<Identifier> {
+    preid
+        .map(Identifier::parse)
+        .into_iter()
+        .chain([Identifier::Numeric(0)])
+        .collect()
+}
+
+// Counts up the last numeric identifier, `rc.9` to `rc.10`, or appends a
+// `0` if there's none.
+fn next_prerelease(pre: &[Identifier]) -> Result<Vec<Identifier>> {
+    let mut rv = pre.to_vec();
+    match rv.iter_mut().rev().find(|i| i.is_numeric()) {
+        Some(Identifier::Numeric(n)) => {
+            *n = n
+                .checked_add(1)
+                .ok_or_else(|| Error::Bump("prerelease number overflows".into()))?;
+        }
+        _ => rv.push(Identifier::Numeric(0)),
+    }
+    Ok(rv)
+}
+
+// Writes out and parses a version, so that `raw` keeps the shape of the
+// one bumped.
+fn render(numbers: &[usize], letter: Option<char>, pre: &[Identifier]) -> Result<Semver> {
+    let mut rv = numbers
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+    rv.extend(letter);
+    if !pre.is_empty() {
+        let pre = pre.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        rv.push_str(&format!("-{}", pre.join(".")));
+    }
+    Semver::parse(&rv)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -18,6 +150,23 @@ pub enum SemverComponent {
     Major,
     Minor,
     Patch,
+    /// The component at this index, counting from 0 for the major, eg. 3
+    /// for the `4` in `1.2.3.4`. The letter of `1.1.1q` is at index 3.
+    Index(usize),
+    /// The letter suffix of OpenSSL-style versions, `1.1.1q` to `1.1.1r`.
+    /// Versions without one get an `a`.
+    Letter,
+    /// The next major's first prerelease, `1.2.3` to `2.0.0-0`.
+    Premajor,
+    /// The next minor's first prerelease, `1.2.3` to `1.3.0-0`.
+    Preminor,
+    /// The next patch's first prerelease, `1.2.3` to `1.2.4-0`.
+    Prepatch,
+    /// The next prerelease, `1.2.3-rc.9` to `1.2.3-rc.10`, or a
+    /// [`SemverComponent::Prepatch`] if the version isn't one.
+    Prerelease,
+    /// The release a prerelease leads up to, `1.2.3-rc.1` to `1.2.3`.
+    Release,
     None,
 }
 
@@ -27,7 +176,16 @@ impl SemverComponent {
             "major" => Ok(Self::Major),
             "minor" => Ok(Self::Minor),
             "patch" => Ok(Self::Patch),
-            _ => Err(Error::Semver(format!("invalid bump component '{}'", s))),
+            "letter" => Ok(Self::Letter),
+            "premajor" => Ok(Self::Premajor),
+            "preminor" => Ok(Self::Preminor),
+            "prepatch" => Ok(Self::Prepatch),
+            "prerelease" => Ok(Self::Prerelease),
+            "release" => Ok(Self::Release),
+            _ => match s.parse::<usize>() {
+                Ok(i) => Ok(Self::Index(i)),
+                Err(_) => Err(Error::Semver(format!("invalid bump component '{}'", s))),
+            },
         }
     }
 }
@@ -0,0 +1,366 @@
+//! Calendar versioning schemes, as described at <https://calver.org>, eg.
+//! `YYYY.0M.MICRO` or `YY.MINOR`.
+//!
+//! A [`CalVer`] describes versions rather than being one: the versions it
+//! checks and produces are plain [`Semver`]s, so they order and match ranges
+//! like any other.
+
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    error::{Error, Result},
+    range::Range,
+};
+
+use super::Semver;
+
+/// One dot-separated part of a scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Token {
+    /// `YYYY`, eg. `2006`.
+    FullYear,
+    /// `YY`, years since 2000, eg. `6` or `16`.
+    ShortYear,
+    /// `0Y`, years since 2000 padded to two digits, eg. `06`.
+    PaddedYear,
+    /// `MM`, eg. `1` or `11`.
+    Month,
+    /// `0M`, eg. `01` or `11`.
+    PaddedMonth,
+    /// `DD`, eg. `1` or `31`.
+    Day,
+    /// `0D`, eg. `01` or `31`.
+    PaddedDay,
+    /// `MAJOR`, `MINOR` and `MICRO` count releases within a date.
+    Major,
+    Minor,
+    Micro,
+}
+
+impl Token {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "YYYY" => Ok(Self::FullYear),
+            "YY" => Ok(Self::ShortYear),
+            "0Y" => Ok(Self::PaddedYear),
+            "MM" => Ok(Self::Month),
+            "0M" => Ok(Self::PaddedMonth),
+            "DD" => Ok(Self::Day),
+            "0D" => Ok(Self::PaddedDay),
+            "MAJOR" => Ok(Self::Major),
+            "MINOR" => Ok(Self::Minor),
+            "MICRO" => Ok(Self::Micro),
+            _ => Err(Error::Semver(format!("unknown calver token '{}'", s))),
+        }
+    }
+
+    pub fn is_date(&self) -> bool {
+        !matches!(self, Self::Major | Self::Minor | Self::Micro)
+    }
+
+    fn is_padded(&self) -> bool {
+        matches!(self, Self::PaddedYear | Self::PaddedMonth | Self::PaddedDay)
+    }
+
+    // The token's value on `date`, or `None` for the counters.
+    fn value(&self, date: &Date) -> Result<Option<usize>> {
+        Ok(match self {
+            Self::FullYear => Some(date.year),
+            Self::ShortYear | Self::PaddedYear => match date.year.checked_sub(2000) {
+                Some(year) => Some(year),
+                None => {
+                    return Err(Error::Semver(format!(
+                        "{self} can't write years before 2000, like {date}'s"
+                    )))
+                }
+            },
+            Self::Month | Self::PaddedMonth => Some(date.month),
+            Self::Day | Self::PaddedDay => Some(date.day),
+            Self::Major | Self::Minor | Self::Micro => None,
+        })
+    }
+
+    fn write(&self, n: usize) -> String {
+        if self.is_padded() {
+            format!("{n:02}")
+        } else {
+            n.to_string()
+        }
+    }
+
+    // Whether `text` is how this token writes some valid value.
+    fn accepts(&self, text: &str) -> bool {
+        if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+        let padded = if self.is_padded() {
+            text.len() >= 2 && (text.len() == 2 || !text.starts_with('0'))
+        } else {
+            text == "0" || !text.starts_with('0')
+        };
+        let Ok(n) = text.parse::<usize>() else {
+            return false;
+        };
+        padded
+            && match self {
+                Self::FullYear => text.len() == 4,
+                Self::Month | Self::PaddedMonth => (1..=12).contains(&n),
+                Self::Day | Self::PaddedDay => (1..=31).contains(&n),
+                _ => true,
+            }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Self::FullYear => "YYYY",
+            Self::ShortYear => "YY",
+            Self::PaddedYear => "0Y",
+            Self::Month => "MM",
+            Self::PaddedMonth => "0M",
+            Self::Day => "DD",
+            Self::PaddedDay => "0D",
+            Self::Major => "MAJOR",
+            Self::Minor => "MINOR",
+            Self::Micro => "MICRO",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A calendar versioning scheme, eg. `YYYY.0M.MICRO`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CalVer {
+    pub tokens: Vec<Token>,
+    pub raw: String,
+}
+
+impl CalVer {
+    pub fn parse(scheme: &str) -> Result<Self> {
+        let tokens = scheme
+            .split('.')
+            .map(Token::parse)
+            .collect::<Result<Vec<_>>>()?;
+        if !tokens.iter().any(Token::is_date) {
+            return Err(Error::Semver(format!("{scheme} has no date in it")));
+        }
+        Ok(Self {
+            tokens,
+            raw: scheme.to_string(),
+        })
+    }
+
+    /// Parses `version`, checking that it's written in this scheme. A
+    /// prerelease or build after it is allowed. `raw` is kept as written,
+    /// so `2026.1` stays `2026.1`.
+    pub fn validate(&self, version: &str) -> Result<Semver> {
+        let mut rv = Semver::parse(version)?;
+        let release = version.split(['-', '+']).next().unwrap_or_default();
+        let parts = release.split('.').collect::<Vec<_>>();
+        if parts.len() != self.tokens.len() {
+            return Err(Error::Semver(format!(
+                "{version} doesn't match {self}: expected {} components",
+                self.tokens.len()
+            )));
+        }
+        for (token, part) in self.tokens.iter().zip(parts) {
+            if !token.accepts(part) {
+                return Err(Error::Semver(format!(
+                    "{version} doesn't match {self}: '{part}' isn't a valid {token}"
+                )));
+            }
+        }
+        rv.raw = version.to_string();
+        Ok(rv)
+    }
+
+    /// The version to release on `date`, after `current` if there's been a
+    /// release before. A release on a later date resets the counters after
+    /// the part of the date that changed to 0; another on the same date
+    /// counts up the last counter.
+    pub fn next(&self, current: Option<&Semver>, date: &Date) -> Result<Semver> {
+        let mut values = self
+            .tokens
+            .iter()
+            .map(|t| t.value(date))
+            .collect::<Result<Vec<_>>>()?;
+
+        if let Some(current) = current {
+            let was = |i: usize| *current.components.get(i).unwrap_or(&0);
+            let dates = |values: &[Option<usize>]| {
+                self.tokens
+                    .iter()
+                    .zip(values)
+                    .enumerate()
+                    .filter(|(_, (t, _))| t.is_date())
+                    .map(|(i, (_, v))| (v.unwrap_or_default(), was(i)))
+                    .collect::<Vec<_>>()
+            };
+            let (new, old): (Vec<_>, Vec<_>) = dates(&values).into_iter().unzip();
+            if new < old {
+                return Err(Error::Bump(format!(
+                    "{date} is before {current} was released"
+                )));
+            }
+            // Counters written before any of the date that changed, like the
+            // MAJOR of MAJOR.YYYY.0M, carry over; the rest start again at 0.
+            let changed = self
+                .tokens
+                .iter()
+                .zip(&values)
+                .enumerate()
+                .find(|(i, (t, v))| t.is_date() && v.unwrap_or_default() != was(*i))
+                .map(|(i, _)| i);
+            if let Some(changed) = changed {
+                for (i, (token, value)) in self.tokens.iter().zip(&mut values).enumerate() {
+                    if !token.is_date() && i < changed {
+                        *value = Some(was(i));
+                    }
+                }
+            }
+            if new == old {
+                let last = self.tokens.iter().rposition(|t| !t.is_date());
+                let Some(last) = last else {
+                    return Err(Error::Bump(format!(
+                        "{current} was already released on {date}"
+                    )));
+                };
+                for (i, (token, value)) in self.tokens.iter().zip(&mut values).enumerate() {
+                    if !token.is_date() {
+                        *value = Some(if i == last {
+                            was(i).checked_add(1).ok_or_else(|| {
+                                Error::Bump(format!("{token} of {current} overflows"))
+                            })?
+                        } else {
+                            was(i)
+                        });
+                    }
+                }
+            }
+        }
+
+        self.render(&values)
+    }
+
+    /// The versions this scheme releases on `date`'s day, month or year,
+    /// depending on how precise the scheme is. Only schemes starting with
+    /// their date have one.
+    pub fn range(&self, date: &Date) -> Result<Range> {
+        let prefix = self.tokens.iter().take_while(|t| t.is_date()).count();
+        if self.tokens[prefix..].iter().any(Token::is_date) {
+            return Err(Error::Range(format!(
+                "{self} doesn't start with its date, so it has no range for {date}"
+            )));
+        }
+        let values = self.tokens[..prefix]
+            .iter()
+            .map(|t| Ok(t.value(date)?.unwrap_or_default().to_string()))
+            .collect::<Result<Vec<_>>>()?
+            .join(".");
+        if prefix == self.tokens.len() {
+            Range::single(&values)
+        } else {
+            Range::parse(&format!("@{values}"))
+        }
+    }
+
+    // Writes out `values`, with the counters that have none at 0.
+    fn render(&self, values: &[Option<usize>]) -> Result<Semver> {
+        let raw = self
+            .tokens
+            .iter()
+            .zip(values)
+            .map(|(t, v)| t.write(v.unwrap_or_default()))
+            .collect::<Vec<_>>()
+            .join(".");
+        let mut rv = Semver::parse(&raw)?;
+        rv.raw = raw;
+        Ok(rv)
+    }
+}
+
+impl fmt::Display for CalVer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+/// A calendar date, written `YYYY-MM-DD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: usize,
+    pub month: usize,
+    pub day: usize,
+}
+
+impl Date {
+    pub fn parse(date: &str) -> Result<Self> {
+        let fail = || Error::Semver(format!("invalid date '{date}', expected YYYY-MM-DD"));
+        let parts = date
+            .split('-')
+            .map(|p| {
+                p.bytes()
+                    .all(|b| b.is_ascii_digit())
+                    .then(|| p.parse::<usize>().ok())
+                    .flatten()
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(fail)?;
+        let [year, month, day] = parts[..] else {
+            return Err(fail());
+        };
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(fail());
+        }
+        Ok(Self { year, month, day })
+    }
+
+    /// Today's date in UTC.
+    #[cfg(not(tarpaulin_include))]
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::from_days((secs / 86_400) as i64)
+    }
+
+    // The date `days` after 1970-01-01, per Howard Hinnant's
+    // `civil_from_days`.
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        Self {
+            year: year as usize,
+            month: month as usize,
+            day: day as usize,
+        }
+    }
+}
+
+fn days_in_month(year: usize, month: usize) -> usize {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
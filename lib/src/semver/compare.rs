@@ -1,5 +1,8 @@
-use super::Semver;
-use std::cmp::Ordering;
+use super::{prerelease::Identifier, Semver};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
 
 impl Semver {
     pub fn neq(&self, other: &Semver) -> bool {
@@ -25,6 +28,23 @@ impl Semver {
         }
     }
 
+    /// Precedence that also orders by build metadata once everything else
+    /// ties, for callers that need `1.2.3+a` and `1.2.3+b` to be distinct.
+    /// Build identifiers compare like prerelease identifiers.
+    pub fn cmp_with_build(&self, other: &Semver) -> Ordering {
+        self.compare(other).then_with(|| {
+            let a = self.build.iter().map(|b| Identifier::parse(b));
+            let b = other.build.iter().map(|b| Identifier::parse(b));
+            a.cmp(b)
+        })
+    }
+
+    /// Equality including build metadata. See [`Semver::cmp_with_build`].
+    pub fn eq_exact(&self, other: &Semver) -> bool {
+        self.cmp_with_build(other) == Ordering::Equal
+    }
+
+    // Precedence per SemVer 2.0 §11: build metadata is ignored.
     fn compare(&self, other: &Semver) -> Ordering {
         let acmps = self.handle_calver();
         let bcmps = other.handle_calver();
@@ -47,6 +67,8 @@ impl Semver {
             return Ordering::Less;
         }
 
+        // Identifiers compare per SemVer 2.0 §11 (numeric < alphanumeric,
+        // numeric by value), and a shorter set of identifiers loses a tie.
         let len = self.prerelease.len().max(other.prerelease.len());
         for x in 0..len {
             let a = self.prerelease.get(x);
@@ -60,19 +82,6 @@ impl Semver {
             }
         }
 
-        let len = self.build.len().max(other.build.len());
-        for x in 0..len {
-            let a = self.build.get(x);
-            let b = other.build.get(x);
-            match (a, b) {
-                (None, _) => return Ordering::Less,
-                (_, None) => return Ordering::Greater,
-                (Some(a), Some(b)) if a > b => return Ordering::Greater,
-                (Some(a), Some(b)) if a < b => return Ordering::Less,
-                _ => continue,
-            }
-        }
-
         Ordering::Equal
     }
 }
@@ -83,6 +92,19 @@ impl PartialEq for Semver {
     }
 }
 
+// Must agree with `compare`: `0.2` == `0.2.0`, and calver majors are shifted.
+impl Hash for Semver {
+    #[cfg(not(tarpaulin_include))]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut cmps = self.handle_calver();
+        while cmps.last() == Some(&0) {
+            cmps.pop();
+        }
+        cmps.hash(state);
+        self.prerelease.hash(state);
+    }
+}
+
 impl Ord for Semver {
     fn cmp(&self, other: &Self) -> Ordering {
         self.compare(other)
@@ -94,3 +116,46 @@ impl PartialOrd for Semver {
         Some(self.cmp(other))
     }
 }
+
+/// A [`Semver`] whose equality, ordering and hash include build metadata,
+/// for keying maps or sets where `1.2.3+a` and `1.2.3+b` must not collide.
+#[derive(Debug, Clone)]
+pub struct BuildSensitive(pub Semver);
+
+impl PartialEq for BuildSensitive {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_exact(&other.0)
+    }
+}
+
+impl Eq for BuildSensitive {}
+
+impl Hash for BuildSensitive {
+    #[cfg(not(tarpaulin_include))]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+        self.0
+            .build
+            .iter()
+            .map(|b| Identifier::parse(b))
+            .for_each(|b| b.hash(state));
+    }
+}
+
+impl Ord for BuildSensitive {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_with_build(&other.0)
+    }
+}
+
+impl PartialOrd for BuildSensitive {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<Semver> for BuildSensitive {
+    fn from(v: Semver) -> Self {
+        Self(v)
+    }
+}
@@ -0,0 +1,165 @@
+//! Debian package versions, `[epoch:]upstream[-revision]`, ordered the way
+//! `dpkg --compare-versions` orders them.
+//!
+//! Upstream versions and revisions compare as alternating runs of
+//! non-digits and digits. Non-digits compare character by character with
+//! letters before everything else and `~` before even the end of the
+//! string, so `1.0~rc1` sorts before `1.0`. Digits compare numerically.
+
+use std::{cmp::Ordering, fmt};
+
+use super::evr;
+use crate::{
+    error::{Diagnostic, Error, ErrorKind, Result},
+    semver::Semver,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct Version {
+    pub epoch: u64,
+    pub upstream: String,
+    pub revision: Option<String>,
+    pub raw: String,
+}
+
+impl Version {
+    pub fn parse(version: &str) -> Result<Self> {
+        let is_upstream = |c: char| c.is_ascii_alphanumeric() || ".+~-:".contains(c);
+        let is_revision = |c: char| c.is_ascii_alphanumeric() || ".+~".contains(c);
+        let evr = evr::parse(version, is_upstream, is_revision)?;
+
+        if let Some(c) = evr.version.chars().next().filter(|c| !c.is_ascii_digit()) {
+            let at = evr.version_at;
+            return Err(Error::Parse(Diagnostic::new(
+                ErrorKind::UnknownCharacter(c),
+                at..at + c.len_utf8(),
+                version,
+            )));
+        }
+
+        Ok(Self {
+            epoch: evr.epoch.unwrap_or(0),
+            upstream: evr.version.to_string(),
+            revision: evr.release.map(String::from),
+            raw: version.to_string(),
+        })
+    }
+
+    /// The Debian version for `semver`: prereleases follow a `~`, so they
+    /// sort before their release, and otherwise in SemVer 2.0 §11's order,
+    /// except that dpkg compares digits within an identifier by value, so
+    /// `rc10` lands above `rc9`.
+    ///
+    /// Fails for versions with build metadata, which a Debian version can't
+    /// carry without changing where it sorts, and for prereleases with a
+    /// `-`, which Debian would read as the start of a revision.
+    pub fn from_semver(semver: &Semver) -> Result<Self> {
+        Self::parse(&evr::from_semver(semver, "a Debian")?)
+    }
+
+    /// The inverse of [`Version::from_semver`]. Fails for versions it
+    /// can't have produced, such as those with an epoch or a revision.
+    pub fn to_semver(&self) -> Result<Semver> {
+        evr::to_semver(
+            &self.raw,
+            self.epoch,
+            &self.upstream,
+            self.revision.as_deref(),
+        )
+    }
+}
+
+// dpkg's `order()`: where a non-digit character (or the end, `None`)
+// sorts.
+fn order(c: Option<u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(c) if c.is_ascii_digit() => 0,
+        Some(c) if c.is_ascii_alphabetic() => c as i32,
+        Some(b'~') => -1,
+        Some(c) => c as i32 + 256,
+    }
+}
+
+// dpkg's `verrevcmp()`.
+fn compare(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    let digit = |s: &[u8], i: usize| s.get(i).is_some_and(u8::is_ascii_digit);
+
+    while i < a.len() || j < b.len() {
+        while (i < a.len() && !digit(a, i)) || (j < b.len() && !digit(b, j)) {
+            let (ac, bc) = (order(a.get(i).copied()), order(b.get(j).copied()));
+            if ac != bc {
+                return ac.cmp(&bc);
+            }
+            i += 1;
+            j += 1;
+        }
+        while a.get(i) == Some(&b'0') {
+            i += 1;
+        }
+        while b.get(j) == Some(&b'0') {
+            j += 1;
+        }
+        let mut first_diff = Ordering::Equal;
+        while digit(a, i) && digit(b, j) {
+            if first_diff.is_eq() {
+                first_diff = a[i].cmp(&b[j]);
+            }
+            i += 1;
+            j += 1;
+        }
+        if digit(a, i) {
+            return Ordering::Greater;
+        }
+        if digit(b, j) {
+            return Ordering::Less;
+        }
+        if first_diff.is_ne() {
+            return first_diff;
+        }
+    }
+    Ordering::Equal
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| compare(&self.upstream, &other.upstream))
+            .then_with(|| {
+                compare(
+                    self.revision.as_deref().unwrap_or(""),
+                    other.revision.as_deref().unwrap_or(""),
+                )
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}:", self.epoch)?;
+        }
+        write!(f, "{}", self.upstream)?;
+        if let Some(revision) = &self.revision {
+            write!(f, "-{revision}")?;
+        }
+        Ok(())
+    }
+}
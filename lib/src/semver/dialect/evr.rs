@@ -0,0 +1,129 @@
+//! The `[epoch:]version[-release]` shape Debian and RPM versions share, and
+//! how a SemVer version is written in it.
+
+use crate::{
+    error::{Diagnostic, Error, ErrorKind, Part, Result},
+    semver::Semver,
+};
+
+pub(super) struct Evr<'a> {
+    pub epoch: Option<u64>,
+    pub version: &'a str,
+    /// Where `version` starts in the input.
+    pub version_at: usize,
+    pub release: Option<&'a str>,
+}
+
+/// Splits `input` at its first `:` and last `-`, checking every character of
+/// the version and release against `version_char` and `release_char`.
+pub(super) fn parse<'a>(
+    input: &'a str,
+    version_char: impl Fn(char) -> bool,
+    release_char: impl Fn(char) -> bool,
+) -> Result<Evr<'a>> {
+    let fail =
+        |kind, span: std::ops::Range<usize>| Err(Error::Parse(Diagnostic::new(kind, span, input)));
+
+    let (epoch, start) = match input.split_once(':') {
+        Some((epoch, _)) => {
+            if epoch.is_empty() {
+                return fail(ErrorKind::EmptyIdentifier(Part::Epoch), 0..0);
+            }
+            if let Some((i, c)) = epoch.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+                return fail(ErrorKind::UnknownCharacter(c), i..i + c.len_utf8());
+            }
+            match epoch.parse::<u64>() {
+                Ok(n) => (Some(n), epoch.len() + 1),
+                Err(_) => return fail(ErrorKind::Overflow(Part::Epoch), 0..epoch.len()),
+            }
+        }
+        None => (None, 0),
+    };
+
+    let rest = &input[start..];
+    let (version, release) = match rest.rsplit_once('-') {
+        Some((version, release)) => (version, Some(release)),
+        None => (rest, None),
+    };
+
+    if version.is_empty() {
+        return fail(ErrorKind::EmptyIdentifier(Part::Component), start..start);
+    }
+    if let Some((i, c)) = version.char_indices().find(|(_, c)| !version_char(*c)) {
+        let i = start + i;
+        return fail(ErrorKind::UnknownCharacter(c), i..i + c.len_utf8());
+    }
+    if let Some(release) = release {
+        let offset = start + version.len() + 1;
+        if release.is_empty() {
+            return fail(ErrorKind::EmptyIdentifier(Part::Revision), offset..offset);
+        }
+        if let Some((i, c)) = release.char_indices().find(|(_, c)| !release_char(*c)) {
+            let i = offset + i;
+            return fail(ErrorKind::UnknownCharacter(c), i..i + c.len_utf8());
+        }
+    }
+
+    Ok(Evr {
+        epoch,
+        version,
+        version_at: start,
+        release,
+    })
+}
+
+/// The version part for `semver`, with its prerelease after a `~`. `scheme`
+/// names the dialect for errors, eg. "a Debian".
+pub(super) fn from_semver(semver: &Semver, scheme: &str) -> Result<String> {
+    write(semver).map_err(|why| {
+        Error::Semver(format!(
+            "cannot write {} as {scheme} version: {why}",
+            semver.raw
+        ))
+    })
+}
+
+/// The inverse of [`from_semver`], for a version written `raw` with these
+/// parts. Fails for any [`from_semver`] can't have produced.
+pub(super) fn to_semver(
+    raw: &str,
+    epoch: u64,
+    version: &str,
+    release: Option<&str>,
+) -> Result<Semver> {
+    let fail = || Error::Semver(format!("{raw} has no SemVer equivalent"));
+    if epoch != 0 || release.is_some() {
+        return Err(fail());
+    }
+    let semver = Semver::parse(&version.replacen('~', "-", 1)).map_err(|_| fail())?;
+    match write(&semver) {
+        Ok(v) if v == version => Ok(semver),
+        _ => Err(fail()),
+    }
+}
+
+// `semver` as a version part, or why it can't be one.
+fn write(semver: &Semver) -> std::result::Result<String, &'static str> {
+    if !semver.build.is_empty() {
+        return Err("it has build metadata");
+    }
+    let mut rv = semver
+        .components
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+    if !semver.prerelease.is_empty() {
+        let pr = semver
+            .prerelease
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        if pr.contains('-') {
+            return Err("its prerelease has a '-'");
+        }
+        rv.push_str(&format!("~{pr}"));
+    }
+    Ok(rv)
+}
@@ -0,0 +1,12 @@
+//! Version schemes other than SemVer, with their own grammar and ordering.
+//!
+//! These don't convert to [`Semver`] in general: their orderings disagree
+//! with it, so each comes with its own type. Debian and RPM versions convert
+//! to and from [`Semver`] where nothing is lost.
+//!
+//! [`Semver`]: super::Semver
+
+pub mod debian;
+mod evr;
+pub mod pep440;
+pub mod rpm;
@@ -0,0 +1,282 @@
+//! Python package versions, per [PEP 440].
+//!
+//! Versions are `[N!]N(.N)*[{a|b|rc}N][.postN][.devN][+local]`, and parsing
+//! accepts the spellings PEP 440 says to normalise (`1.0-RC1`, `1.0.alpha`,
+//! `1.0-1`, `v1.0`, `1.0_post2` and so on). Ordering follows the PEP: a
+//! release sorts after its dev releases and pre-releases and before its
+//! post-releases, epochs override everything else, and trailing zeros in
+//! the release are insignificant.
+//!
+//! [PEP 440]: https://peps.python.org/pep-0440/
+
+use std::{cmp::Ordering, fmt};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::{Diagnostic, Error, ErrorKind, Part, Result};
+
+// Everything from `v` to the local label; anchored at the end for parsing,
+// and not for finding where a bad version goes wrong.
+const PATTERN: &str = r"(?ix)^
+    v?
+    (?:(?P<epoch>[0-9]+)!)?
+    (?P<release>[0-9]+(?:\.[0-9]+)*)
+    (?:[-_.]?(?P<pre>alpha|a|beta|b|preview|pre|c|rc)[-_.]?(?P<pre_n>[0-9]+)?)?
+    (?:-(?P<post_implicit>[0-9]+)|[-_.]?(?P<post>post|rev|r)[-_.]?(?P<post_n>[0-9]+)?)?
+    (?:[-_.]?(?P<dev>dev)[-_.]?(?P<dev_n>[0-9]+)?)?
+    (?:\+(?P<local>[a-z0-9]+(?:[-_.][a-z0-9]+)*))?";
+
+lazy_static! {
+    static ref FULL_REGEX: Regex = Regex::new(&format!("{PATTERN}$")).unwrap();
+    static ref PREFIX_REGEX: Regex = Regex::new(PATTERN).unwrap();
+}
+
+/// A pre-release phase, in the order they sort.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Alpha,
+    Beta,
+    Rc,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Phase::Alpha => "a",
+            Phase::Beta => "b",
+            Phase::Rc => "rc",
+        };
+        write!(f, "{s}")
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Version {
+    pub epoch: u64,
+    pub release: Vec<u64>,
+    pub pre: Option<(Phase, u64)>,
+    pub post: Option<u64>,
+    pub dev: Option<u64>,
+    /// Local version label segments, lowercased.
+    pub local: Vec<String>,
+    pub raw: String,
+}
+
+impl Version {
+    pub fn parse(version: &str) -> Result<Self> {
+        let trimmed = version.trim_start();
+        let start = version.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+
+        let captures = FULL_REGEX
+            .captures(trimmed)
+            .ok_or_else(|| locate(version, start))?;
+
+        let number = |name: &str| -> Result<Option<u64>> {
+            let Some(m) = captures.name(name) else {
+                return Ok(None);
+            };
+            m.as_str().parse::<u64>().map(Some).map_err(|_| {
+                Error::Parse(Diagnostic::new(
+                    ErrorKind::Overflow(Part::Component),
+                    start + m.start()..start + m.end(),
+                    version,
+                ))
+            })
+        };
+
+        let mut release = Vec::new();
+        let mut offset = captures.name("release").map_or(0, |m| m.start());
+        for n in captures["release"].split('.') {
+            let span = start + offset..start + offset + n.len();
+            offset += n.len() + 1;
+            release.push(n.parse::<u64>().map_err(|_| {
+                Error::Parse(Diagnostic::new(
+                    ErrorKind::Overflow(Part::Component),
+                    span,
+                    version,
+                ))
+            })?);
+        }
+
+        // A missing number after a marker means 0, eg. `1.0rc` is `1.0rc0`.
+        let pre = match captures.name("pre") {
+            Some(phase) => Some((phase_of(phase.as_str()), number("pre_n")?.unwrap_or(0))),
+            None => None,
+        };
+        let post = match captures.name("post") {
+            Some(_) => Some(number("post_n")?.unwrap_or(0)),
+            None => number("post_implicit")?,
+        };
+        let dev = match captures.name("dev") {
+            Some(_) => Some(number("dev_n")?.unwrap_or(0)),
+            None => None,
+        };
+        let local = match captures.name("local") {
+            Some(l) => l
+                .as_str()
+                .to_ascii_lowercase()
+                .split(['-', '_', '.'])
+                .map(String::from)
+                .collect(),
+            None => vec![],
+        };
+
+        Ok(Self {
+            epoch: number("epoch")?.unwrap_or(0),
+            release,
+            pre,
+            post,
+            dev,
+            local,
+            raw: trimmed.to_string(),
+        })
+    }
+
+    /// Whether this is a pre-release or a dev release.
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    pub fn is_postrelease(&self) -> bool {
+        self.post.is_some()
+    }
+
+    /// The version without its local label.
+    pub fn public(&self) -> Self {
+        Self {
+            local: vec![],
+            ..self.clone()
+        }
+    }
+
+    /// Just the epoch and release, eg. `1.2` for `1.2rc1.post3+ubuntu`.
+    pub fn base(&self) -> Self {
+        Self {
+            epoch: self.epoch,
+            release: self.release.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+fn phase_of(s: &str) -> Phase {
+    match s.to_ascii_lowercase().as_str() {
+        "a" | "alpha" => Phase::Alpha,
+        "b" | "beta" => Phase::Beta,
+        _ => Phase::Rc,
+    }
+}
+
+// Points at the first byte that can't continue a valid version, for inputs
+// the regex rejected. `start` skips leading whitespace.
+fn locate(version: &str, start: usize) -> Error {
+    let rest = &version[start..];
+    let at = start + PREFIX_REGEX.find(rest).map_or(0, |m| m.end());
+    match version[at..].chars().next() {
+        Some(c) => Error::Parse(Diagnostic::new(
+            ErrorKind::UnknownCharacter(c),
+            at..at + c.len_utf8(),
+            version,
+        )),
+        None => Error::Parse(Diagnostic::new(
+            ErrorKind::EmptyIdentifier(Part::Component),
+            at..at,
+            version,
+        )),
+    }
+}
+
+// Local segments compare numerically when numeric, lexically otherwise, and
+// numeric ones sort after the rest.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Local<'a> {
+    Alpha(&'a str),
+    Numeric(u64),
+}
+
+impl Version {
+    // Where the pre-release part puts the version among its siblings: dev
+    // releases of the final release first, then pre-releases, then the rest.
+    fn pre_key(&self) -> (u8, Option<(Phase, u64)>) {
+        match (self.pre, self.post, self.dev) {
+            (None, None, Some(_)) => (0, None),
+            (Some(pre), _, _) => (1, Some(pre)),
+            (None, _, _) => (2, None),
+        }
+    }
+
+    fn local_key(&self) -> Vec<Local<'_>> {
+        self.local
+            .iter()
+            .map(|s| match s.parse::<u64>() {
+                Ok(n) => Local::Numeric(n),
+                Err(_) => Local::Alpha(s),
+            })
+            .collect()
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.release.len().max(other.release.len());
+        let release = |v: &Self| {
+            (0..len)
+                .map(|i| v.release.get(i).copied().unwrap_or(0))
+                .collect::<Vec<_>>()
+        };
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| release(self).cmp(&release(other)))
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            .then_with(|| self.post.cmp(&other.post))
+            // No dev part sorts after any.
+            .then_with(|| self.dev.is_none().cmp(&other.dev.is_none()))
+            .then_with(|| self.dev.cmp(&other.dev))
+            .then_with(|| self.local_key().cmp(&other.local_key()))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+/// The normalised form, eg. `1.0rc1.post0` for `1.0-RC1-r`.
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+        let release = self
+            .release
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        write!(f, "{release}")?;
+        if let Some((phase, n)) = self.pre {
+            write!(f, "{phase}{n}")?;
+        }
+        if let Some(n) = self.post {
+            write!(f, ".post{n}")?;
+        }
+        if let Some(n) = self.dev {
+            write!(f, ".dev{n}")?;
+        }
+        if !self.local.is_empty() {
+            write!(f, "+{}", self.local.join("."))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+//! RPM package versions, `[epoch:]version[-release]`, ordered the way
+//! `rpmvercmp` orders them.
+//!
+//! Versions and releases compare as runs of digits or letters, with any
+//! other characters only separating them. Digit runs compare numerically
+//! and beat letter runs; letter runs compare as strings. `~` sorts before
+//! everything, even the end of the string, and `^` after the end but
+//! before anything else, so `1.0~rc1 < 1.0 < 1.0^git1 < 1.0.1`.
+
+use std::{cmp::Ordering, fmt};
+
+use super::evr;
+use crate::{error::Result, semver::Semver};
+
+#[derive(Debug, Clone, Default)]
+pub struct Version {
+    pub epoch: u64,
+    pub version: String,
+    pub release: Option<String>,
+    pub raw: String,
+}
+
+impl Version {
+    pub fn parse(version: &str) -> Result<Self> {
+        let valid = |c: char| c.is_ascii_alphanumeric() || "._+~^".contains(c);
+        let evr = evr::parse(version, valid, valid)?;
+
+        Ok(Self {
+            epoch: evr.epoch.unwrap_or(0),
+            version: evr.version.to_string(),
+            release: evr.release.map(String::from),
+            raw: version.to_string(),
+        })
+    }
+
+    /// The RPM version for `semver`: prereleases follow a `~`, so they sort
+    /// before their release. Past that, `rpmvercmp` doesn't always agree
+    /// with SemVer 2.0 §11: it ranks numbers above letters, so
+    /// `1.0.0-alpha.1` converts to a version above `1.0.0-alpha.beta`'s, and
+    /// compares digits within an identifier by value, so `rc10` lands above
+    /// `rc9`.
+    ///
+    /// Fails for versions with build metadata, which an RPM version can't
+    /// carry without changing where it sorts, and for prereleases with a
+    /// `-`, which RPM would read as the start of a release.
+    pub fn from_semver(semver: &Semver) -> Result<Self> {
+        Self::parse(&evr::from_semver(semver, "an RPM")?)
+    }
+
+    /// The inverse of [`Version::from_semver`]. Fails for versions it
+    /// can't have produced, such as those with an epoch or a release.
+    pub fn to_semver(&self) -> Result<Semver> {
+        evr::to_semver(
+            &self.raw,
+            self.epoch,
+            &self.version,
+            self.release.as_deref(),
+        )
+    }
+}
+
+// `rpmvercmp()`.
+fn compare(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    let separator = |c: &u8| !c.is_ascii_alphanumeric() && *c != b'~' && *c != b'^';
+
+    while i < a.len() || j < b.len() {
+        while a.get(i).is_some_and(separator) {
+            i += 1;
+        }
+        while b.get(j).is_some_and(separator) {
+            j += 1;
+        }
+
+        // `~` sorts before anything, even the end.
+        match (a.get(i), b.get(j)) {
+            (Some(b'~'), Some(b'~')) => {
+                i += 1;
+                j += 1;
+                continue;
+            }
+            (Some(b'~'), _) => return Ordering::Less,
+            (_, Some(b'~')) => return Ordering::Greater,
+            _ => {}
+        }
+        // `^` sorts after the end, and before anything else.
+        match (a.get(i), b.get(j)) {
+            (Some(b'^'), Some(b'^')) => {
+                i += 1;
+                j += 1;
+                continue;
+            }
+            (None, Some(b'^')) => return Ordering::Less,
+            (Some(b'^'), None) => return Ordering::Greater,
+            (Some(b'^'), _) => return Ordering::Less,
+            (_, Some(b'^')) => return Ordering::Greater,
+            _ => {}
+        }
+        if i >= a.len() || j >= b.len() {
+            break;
+        }
+
+        let numeric = a[i].is_ascii_digit();
+        let (x, y) = (run(a, i, numeric), run(b, j, numeric));
+        i += x.len();
+        j += y.len();
+
+        // Runs of different kinds: numbers are newer.
+        if y.is_empty() {
+            return if numeric {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+        let rv = if numeric {
+            let trim = |s: &[u8]| {
+                let zeros = s.iter().take_while(|c| **c == b'0').count();
+                s[zeros..].to_vec()
+            };
+            let (x, y) = (trim(x), trim(y));
+            x.len().cmp(&y.len()).then_with(|| x.cmp(&y))
+        } else {
+            x.cmp(y)
+        };
+        if rv.is_ne() {
+            return rv;
+        }
+    }
+
+    match (i >= a.len(), j >= b.len()) {
+        (true, true) => Ordering::Equal,
+        (false, _) => Ordering::Greater,
+        (true, false) => Ordering::Less,
+    }
+}
+
+// The digits, or the letters, starting at `s[start]`.
+fn run(s: &[u8], start: usize, numeric: bool) -> &[u8] {
+    let len = s[start..]
+        .iter()
+        .take_while(|c| {
+            if numeric {
+                c.is_ascii_digit()
+            } else {
+                c.is_ascii_alphabetic()
+            }
+        })
+        .count();
+    &s[start..start + len]
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| compare(&self.version, &other.version))
+            .then_with(|| {
+                compare(
+                    self.release.as_deref().unwrap_or(""),
+                    other.release.as_deref().unwrap_or(""),
+                )
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}:", self.epoch)?;
+        }
+        write!(f, "{}", self.version)?;
+        if let Some(release) = &self.release {
+            write!(f, "-{release}")?;
+        }
+        Ok(())
+    }
+}
@@ -3,8 +3,13 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
 pub mod bump;
+pub mod calver;
 pub mod compare;
+pub mod dialect;
 pub mod parse;
+pub mod prerelease;
+
+use prerelease::Identifier;
 
 #[derive(Default, Debug, Clone, Eq)]
 pub struct Semver {
@@ -14,7 +19,7 @@ pub struct Semver {
     pub minor: usize,
     pub patch: usize,
 
-    pub prerelease: Vec<String>,
+    pub prerelease: Vec<Identifier>,
     pub build: Vec<String>,
 
     pub raw: String,
@@ -1,8 +1,8 @@
-use crate::error::{Error, Result};
+use crate::error::{Diagnostic, Error, ErrorKind, Part, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use super::Semver;
+use super::{prerelease::Identifier, Semver};
 
 lazy_static! {
     static ref FULL_REGEX: Regex =
@@ -14,19 +14,26 @@ lazy_static! {
 impl Semver {
     pub fn parse(semver: &str) -> Result<Self> {
         let raw = semver.trim_start_matches('v').to_string();
+        let prefix = semver.len() - raw.len();
 
-        let captures = FULL_REGEX
-            .captures(&raw)
-            .ok_or_else(|| Error::Semver("invalid semver".into()))?;
+        let captures = FULL_REGEX.captures(&raw).ok_or_else(|| locate(semver))?;
 
+        let mut offset = prefix;
         let mut components: Vec<usize> = captures
             .get(1)
             .ok_or_else(|| Error::Semver("regex failure".into()))?
             .as_str()
             .split('.')
             .map(|s| {
-                s.parse::<usize>()
-                    .map_err(|_| Error::Semver("invalid digit".into()))
+                let span = offset..offset + s.len();
+                offset = span.end + 1;
+                s.parse::<usize>().map_err(|_| {
+                    Error::Parse(Diagnostic::new(
+                        ErrorKind::Overflow(Part::Component),
+                        span,
+                        semver,
+                    ))
+                })
             })
             .collect::<Result<Vec<usize>>>()?;
 
@@ -49,7 +56,7 @@ impl Semver {
         let patch = *components.get(2).unwrap_or(&0);
 
         let prerelease = if let Some(pr) = captures.get(3) {
-            pr.as_str().split('.').map(|s| s.to_string()).collect()
+            pr.as_str().split('.').map(Identifier::parse).collect()
         } else {
             vec![]
         };
@@ -63,7 +70,12 @@ impl Semver {
         let raw = if SHORT_REGEX.is_match(&raw) {
             let mut r = format!("{major}.{minor}.{patch}");
             if !prerelease.is_empty() {
-                r.push_str(&format!("-{}", prerelease.join(".")));
+                let pr = prerelease
+                    .iter()
+                    .map(|i: &Identifier| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(".");
+                r.push_str(&format!("-{pr}"));
             }
             if !build.is_empty() {
                 r.push_str(&format!("+{}", build.join(".")));
@@ -84,7 +96,141 @@ impl Semver {
         })
     }
 
+    /// Parses exactly `MAJOR.MINOR.PATCH[-PRERELEASE][+BUILD]` per the
+    /// SemVer 2.0 grammar, rejecting the pkgx extensions [`Semver::parse`]
+    /// accepts (`v` prefixes, more or fewer than three components, letter
+    /// suffixes). The error names the rule that failed and where.
+    pub fn parse_strict(semver: &str) -> Result<Self> {
+        strict(semver)?;
+        Self::parse(semver)
+    }
+
     pub fn from(input: (usize, usize, usize)) -> Result<Self> {
         Self::parse(&format!("{}.{}.{}", input.0, input.1, input.2))
     }
 }
+
+// Points at the first byte the lenient grammar can't accept, for inputs that
+// `FULL_REGEX` rejected.
+fn locate(semver: &str) -> Error {
+    let bytes = semver.as_bytes();
+    let at = |kind: ErrorKind, i: usize| Error::Parse(Diagnostic::new(kind, i..i, semver));
+    let unexpected = |i: usize| match semver[i..].chars().next() {
+        Some(c) => Error::Parse(Diagnostic::new(
+            ErrorKind::UnknownCharacter(c),
+            i..i + c.len_utf8(),
+            semver,
+        )),
+        None => at(ErrorKind::EmptyIdentifier(Part::Component), i),
+    };
+
+    let mut i = semver.len() - semver.trim_start_matches('v').len();
+    loop {
+        let start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == start {
+            return match bytes.get(i) {
+                Some(b'.') | None => at(ErrorKind::EmptyIdentifier(Part::Component), i),
+                _ => unexpected(i),
+            };
+        }
+        if bytes.get(i) != Some(&b'.') {
+            break;
+        }
+        i += 1;
+    }
+    if bytes.get(i).is_some_and(u8::is_ascii_lowercase) {
+        i += 1;
+    }
+    for (sep, part) in [(b'-', Part::Prerelease), (b'+', Part::Build)] {
+        if bytes.get(i) != Some(&sep) {
+            continue;
+        }
+        i += 1;
+        let start = i;
+        while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || b"-.".contains(&bytes[i])) {
+            i += 1;
+        }
+        if i == start {
+            return at(ErrorKind::EmptyIdentifier(part), i);
+        }
+    }
+    unexpected(i)
+}
+
+fn strict(semver: &str) -> Result<()> {
+    let fail = |kind: ErrorKind, span: std::ops::Range<usize>| {
+        Err(Error::Parse(Diagnostic::new(kind, span, semver)))
+    };
+
+    if semver.starts_with('v') {
+        return fail(ErrorKind::Prefix, 0..1);
+    }
+
+    let (rest, build) = match semver.split_once('+') {
+        Some((rest, build)) => (rest, Some(build)),
+        None => (semver, None),
+    };
+    let (core, prerelease) = match rest.split_once('-') {
+        Some((core, prerelease)) => (core, Some(prerelease)),
+        None => (rest, None),
+    };
+
+    let parts = core.split('.').collect::<Vec<_>>();
+    if parts.len() != 3 {
+        return fail(ErrorKind::ComponentCount(parts.len()), 0..core.len());
+    }
+    let mut offset = 0;
+    for (part, name) in parts.iter().zip([Part::Major, Part::Minor, Part::Patch]) {
+        let span = offset..offset + part.len();
+        offset = span.end + 1;
+        if part.is_empty() {
+            return fail(ErrorKind::EmptyIdentifier(name), span);
+        }
+        if let Some((i, c)) = part.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            let start = span.start + i;
+            return fail(ErrorKind::UnknownCharacter(c), start..start + c.len_utf8());
+        }
+        if part.len() > 1 && part.starts_with('0') {
+            return fail(ErrorKind::LeadingZero(name), span);
+        }
+        if part.parse::<usize>().is_err() {
+            return fail(ErrorKind::Overflow(name), span);
+        }
+    }
+
+    for (identifiers, name, start) in [
+        (prerelease, Part::Prerelease, core.len() + 1),
+        (build, Part::Build, rest.len() + 1),
+    ] {
+        let Some(identifiers) = identifiers else {
+            continue;
+        };
+        let mut offset = start;
+        for identifier in identifiers.split('.') {
+            let span = offset..offset + identifier.len();
+            offset = span.end + 1;
+            if identifier.is_empty() {
+                return fail(ErrorKind::EmptyIdentifier(name), span);
+            }
+            if let Some((i, c)) = identifier
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_alphanumeric() && *c != '-')
+            {
+                let start = span.start + i;
+                return fail(ErrorKind::UnknownCharacter(c), start..start + c.len_utf8());
+            }
+            if name == Part::Prerelease
+                && identifier.len() > 1
+                && identifier.starts_with('0')
+                && identifier.bytes().all(|b| b.is_ascii_digit())
+            {
+                return fail(ErrorKind::LeadingZero(name), span);
+            }
+        }
+    }
+
+    Ok(())
+}
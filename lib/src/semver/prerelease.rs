@@ -0,0 +1,72 @@
+use std::{cmp::Ordering, fmt};
+
+/// A single dot-separated prerelease identifier, eg. the `rc` and `10` in
+/// `1.0.0-rc.10`.
+///
+/// Precedence follows SemVer 2.0 §11: numeric identifiers compare
+/// numerically, alphanumeric identifiers compare lexically in ASCII order,
+/// and numeric identifiers always have lower precedence than alphanumeric
+/// ones.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl Identifier {
+    /// Classifies an identifier. Only digit strings without a leading zero
+    /// (or `0` itself) are numeric; anything else, including `01` and values
+    /// that overflow a `u64`, is kept verbatim as alphanumeric so the input
+    /// round-trips.
+    pub fn parse(s: &str) -> Self {
+        let numeric = !s.is_empty()
+            && s.bytes().all(|b| b.is_ascii_digit())
+            && (s.len() == 1 || !s.starts_with('0'));
+        match s.parse::<u64>() {
+            Ok(n) if numeric => Self::Numeric(n),
+            _ => Self::AlphaNumeric(s.to_string()),
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Self::Numeric(_))
+    }
+}
+
+impl From<&str> for Identifier {
+    fn from(s: &str) -> Self {
+        Self::parse(s)
+    }
+}
+
+impl From<u64> for Identifier {
+    fn from(n: u64) -> Self {
+        Self::Numeric(n)
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Numeric(n) => write!(f, "{n}"),
+            Self::AlphaNumeric(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Numeric(a), Self::Numeric(b)) => a.cmp(b),
+            (Self::Numeric(_), Self::AlphaNumeric(_)) => Ordering::Less,
+            (Self::AlphaNumeric(_), Self::Numeric(_)) => Ordering::Greater,
+            (Self::AlphaNumeric(a), Self::AlphaNumeric(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,128 @@
+use crate::semver::{
+    calver::{CalVer, Date, Token},
+    Semver,
+};
+use anyhow::Result;
+
+#[test]
+fn test_parse() -> Result<()> {
+    let a = CalVer::parse("YYYY.0M.MICRO")?;
+    assert_eq!(
+        a.tokens,
+        [Token::FullYear, Token::PaddedMonth, Token::Micro]
+    );
+    assert_eq!(a.to_string(), "YYYY.0M.MICRO");
+
+    assert!(CalVer::parse("YY.0M.0D").is_ok());
+    assert!(CalVer::parse("YYYY.MINOR").is_ok());
+    assert!(CalVer::parse("YYYY.QQ").is_err());
+    assert!(CalVer::parse("MAJOR.MINOR").is_err());
+    assert!(CalVer::parse("").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_validate() -> Result<()> {
+    let a = CalVer::parse("YYYY.0M.MICRO")?;
+    let b = CalVer::parse("YY.MM.DD")?;
+    let c = CalVer::parse("YYYY.MINOR")?;
+
+    assert_eq!(a.validate("2026.01.3")?.raw, "2026.01.3");
+    assert_eq!(a.validate("2026.10.0-rc.1")?.prerelease.len(), 2);
+    assert!(a.validate("2026.1.3").is_err());
+    assert!(a.validate("2026.13.3").is_err());
+    assert!(a.validate("26.01.3").is_err());
+    assert!(a.validate("2026.01").is_err());
+    assert!(a.validate("2026.01.03").is_err());
+
+    assert!(b.validate("26.10.18").is_ok());
+    assert!(b.validate("26.10.32").is_err());
+    assert!(b.validate("26.0.1").is_err());
+
+    // `raw` keeps the scheme's shape rather than padding to three parts.
+    assert_eq!(c.validate("2026.1")?.raw, "2026.1");
+
+    Ok(())
+}
+
+#[test]
+fn test_next() -> Result<()> {
+    let a = CalVer::parse("YYYY.0M.MICRO")?;
+    let b = CalVer::parse("0Y.MINOR.MICRO")?;
+    let c = CalVer::parse("YYYY.MM.DD")?;
+    let date = Date::parse("2026-10-18")?;
+    let next = |s: &CalVer, v: Option<&str>| -> Result<String> {
+        let v = v.map(|v| s.validate(v)).transpose()?;
+        Ok(s.next(v.as_ref(), &date)?.raw)
+    };
+
+    assert_eq!(next(&a, None)?, "2026.10.0");
+    assert_eq!(next(&a, Some("2026.10.3"))?, "2026.10.4");
+    assert_eq!(next(&a, Some("2026.09.3"))?, "2026.10.0");
+    assert!(next(&a, Some("2026.11.0")).is_err());
+
+    assert_eq!(next(&b, None)?, "26.0.0");
+    assert_eq!(next(&b, Some("26.2.9"))?, "26.2.10");
+    assert_eq!(next(&b, Some("25.2.9"))?, "26.0.0");
+
+    assert_eq!(next(&c, Some("2026.10.17"))?, "2026.10.18");
+    assert!(next(&c, Some("2026.10.18")).is_err());
+
+    // Counters before the date carry over when it changes.
+    let d = CalVer::parse("MAJOR.YYYY.0M")?;
+    assert_eq!(next(&d, Some("3.2026.09"))?, "3.2026.10");
+    assert_eq!(next(&d, Some("3.2026.10"))?, "4.2026.10");
+    let e = CalVer::parse("MAJOR.YYYY.MINOR.0M.MICRO")?;
+    assert_eq!(next(&e, Some("3.2026.4.09.7"))?, "3.2026.4.10.0");
+    assert_eq!(next(&e, Some("3.2025.4.12.7"))?, "3.2026.0.10.0");
+
+    assert!(CalVer::parse("YY.MICRO")?
+        .next(None, &Date::parse("1999-12-31")?)
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_semver_and_range() -> Result<()> {
+    let a = CalVer::parse("YYYY.0M.MICRO")?;
+    let date = Date::parse("2026-10-18")?;
+
+    let old = a.validate("2026.09.7")?;
+    let new = a.next(Some(&old), &date)?;
+    assert!(new.gt(&old));
+    // pkgx sorts calver below every other version.
+    assert!(new.lt(&Semver::parse("1.0.0")?));
+
+    let range = a.range(&date)?;
+    assert!(range.satisfies(&new));
+    assert!(range.satisfies(&a.validate("2026.10.12")?));
+    assert!(!range.satisfies(&old));
+    assert!(!range.satisfies(&a.validate("2026.11.0")?));
+
+    let days = CalVer::parse("YYYY.0M.0D")?.range(&date)?;
+    assert!(days.satisfies(&Semver::parse("2026.10.18")?));
+    assert!(!days.satisfies(&Semver::parse("2026.10.19")?));
+
+    assert!(CalVer::parse("MAJOR.YYYY")?.range(&date).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_date() -> Result<()> {
+    let a = Date::parse("2024-02-29")?;
+    assert_eq!((a.year, a.month, a.day), (2024, 2, 29));
+    assert_eq!(a.to_string(), "2024-02-29");
+    assert!(Date::parse("2026-10-18")? > a);
+
+    assert!(Date::parse("2023-02-29").is_err());
+    assert!(Date::parse("1900-02-29").is_err());
+    assert!(Date::parse("2026-13-01").is_err());
+    assert!(Date::parse("2026-10").is_err());
+    assert!(Date::parse("2026-10-+1").is_err());
+    assert!(Date::parse("today").is_err());
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::{
+    range::{dialect::cargo, Range},
+    semver::Semver,
+    Diagnostic, Error, ErrorKind, Part,
+};
+use anyhow::Result;
+
+// From the Cargo book's "Specifying Dependencies", written as equivalent
+// pkgx ranges.
+#[test]
+fn test_parse() -> Result<()> {
+    let cases = [
+        ("1.2.3", ">=1.2.3<2"),
+        ("1.2", "^1.2"),
+        ("1", "^1"),
+        ("0.2.3", ">=0.2.3<0.3"),
+        ("0.2", "~0.2"),
+        ("0.0.3", "@0.0.3"),
+        ("0.0", "~0.0"),
+        ("0", "^0"),
+        ("^1.2.3", ">=1.2.3<2"),
+        ("~1.2.3", ">=1.2.3<1.3"),
+        ("~1.2", "~1.2"),
+        ("~1", "^1"),
+        ("*", "*"),
+        ("1.*", "^1"),
+        ("1.2.*", "~1.2"),
+        ("=1.2.3", "=1.2.3"),
+        ("=1.2", "~1.2"),
+        (">=1.2.0", ">=1.2"),
+        (">1", ">=2"),
+        (">1.2", ">=1.3"),
+        ("<1.2.3", "<1.2.3"),
+        ("<=1.2.3", "<1.2.3,=1.2.3"),
+        ("<= 1.2", "<1.3"),
+        (">= 1.2, < 1.5", ">=1.2<1.5"),
+        (">=1.2.3,<2", ">=1.2.3<2"),
+        ("^1.2, ~1.4", "~1.4"),
+        ("^1, ^2", "<0"),
+    ];
+
+    for (req, pkgx) in cases {
+        let got = Range::parse_cargo(req)?;
+        let want = Range::parse(pkgx)?;
+        assert!(got.equivalent(&want), "{req}: got {got}, want {want}");
+    }
+
+    // Strict lower bounds on full versions stay strict.
+    assert_eq!(Range::parse_cargo(">1.2.3")?.raw, ">1.2.3");
+
+    Ok(())
+}
+
+#[test]
+fn test_satisfies() -> Result<()> {
+    let cases = [
+        ("1.2", "1.9.0", true),
+        ("1.2", "2.0.0", false),
+        ("=1.2.3", "1.2.3+build", true),
+        (">=1.0.0, <2.0.0", "1.5.0-alpha", false),
+        (">=1.0.0, <2.0.0", "2.0.0-alpha", false),
+        ("^1.2.3-alpha", "1.2.3-beta", true),
+        ("^1.2.3-alpha", "1.2.3", true),
+        ("^1.2.3-alpha", "1.2.4-beta", false),
+        ("^1.2.3-alpha", "2.0.0-alpha", false),
+        (">=1.2.3-rc.1, <1.2.4", "1.2.3-rc.2", true),
+        (">=1.2.3-rc.1, <1.2.4", "1.2.3-alpha", false),
+        ("*", "1.0.0-alpha", false),
+    ];
+
+    for (req, version, expected) in cases {
+        let semver = Semver::parse(version)?;
+        assert_eq!(
+            cargo::satisfies(req, &semver)?,
+            expected,
+            "{req} satisfies {version}"
+        );
+    }
+
+    // The parsed range keeps the rule when asked, and without it,
+    // prereleases match by precedence.
+    for (req, version, expected) in cases {
+        let semver = Semver::parse(version)?;
+        let range = Range::parse_cargo(req)?;
+        assert_eq!(range.satisfies_with(&semver, &cargo::OPTIONS), expected);
+    }
+    let range = Range::parse_cargo("^1.2")?;
+    assert!(range.satisfies(&Semver::parse("1.3.0-beta")?));
+    assert!(!range.satisfies_with(&Semver::parse("1.3.0-beta")?, &cargo::OPTIONS));
+
+    Ok(())
+}
+
+#[test]
+fn test_to_cargo() -> Result<()> {
+    let cases = [
+        ("*", "*"),
+        ("^1", "^1.0.0"),
+        ("^0.2", "^0.2.0"),
+        ("@0.0.3", "^0.0.3"),
+        ("~1.2", "~1.2.0"),
+        ("~1.2.3", "~1.2.3"),
+        ("=1.2.3", "=1.2.3"),
+        (">=1.2", ">=1.2.0"),
+        ("<1.5", "<1.5.0"),
+        (">=1.2<1.5", ">=1.2.0, <1.5.0"),
+        ("<1.5,=1.5", "<=1.5.0"),
+        (">=1.2<1.5,=1.5", ">=1.2.0, <=1.5.0"),
+        ("^1.2.3-rc.1", "^1.2.3-rc.1"),
+        ("<0", "<0.0.0"),
+        (">=18446744073709551615", ">=18446744073709551615.0.0"),
+    ];
+
+    for (pkgx, req) in cases {
+        let range = Range::parse(pkgx)?;
+        assert_eq!(range.to_cargo()?, req, "{pkgx}");
+        assert!(Range::parse_cargo(req)?.equivalent(&range), "{pkgx}");
+    }
+
+    for pkgx in ["^1,^3", "=1.2.3.4", ">=1.2.3.4"] {
+        assert!(
+            matches!(Range::parse(pkgx)?.to_cargo(), Err(Error::Range(_))),
+            "{pkgx}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    let cases = [
+        ("", ErrorKind::EmptyConstraint, 0..0),
+        (">=1.2,", ErrorKind::EmptyConstraint, 6..6),
+        ("~>1.2", ErrorKind::BadOperator("~>".into()), 0..2),
+        (">=1.2, =>1.4", ErrorKind::BadOperator("=>".into()), 7..9),
+        (">=1.2 <1.4", ErrorKind::UnknownCharacter(' '), 5..6),
+        ("1.2.3.4", ErrorKind::UnknownCharacter('.'), 5..6),
+        (
+            "18446744073709551615",
+            ErrorKind::Overflow(Part::Component),
+            0..20,
+        ),
+        (
+            "~0.18446744073709551615",
+            ErrorKind::Overflow(Part::Component),
+            1..23,
+        ),
+    ];
+
+    for (input, kind, span) in cases {
+        assert_eq!(
+            Range::parse_cargo(input),
+            Err(Error::Parse(Diagnostic::new(kind, span, input))),
+            "{input}"
+        );
+    }
+}
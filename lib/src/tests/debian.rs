@@ -0,0 +1,122 @@
+use crate::{
+    semver::{dialect::debian::Version, Semver},
+    Diagnostic, Error, ErrorKind, Part,
+};
+use anyhow::Result;
+use std::cmp::Ordering;
+
+// Mostly from dpkg's `lib/dpkg/t/t-version.c`.
+#[test]
+fn test_compare() -> Result<()> {
+    let cases = [
+        ("0", "0", Ordering::Equal),
+        ("0", "00", Ordering::Equal),
+        ("1.0", "1.00", Ordering::Equal),
+        ("1:0", "0:0", Ordering::Greater),
+        ("1:1.0", "2.0", Ordering::Greater),
+        ("0:1.0", "1.0", Ordering::Equal),
+        ("1.0-1", "1.0-2", Ordering::Less),
+        ("1.0-1", "1.0", Ordering::Greater),
+        ("1.0-0", "1.0", Ordering::Equal),
+        ("1.0", "1.0.1", Ordering::Less),
+        ("1.2", "1.10", Ordering::Less),
+        ("1.0a", "1.0", Ordering::Greater),
+        ("1.0a", "1.0b", Ordering::Less),
+        ("1.0a", "1.0.", Ordering::Less),
+        ("1.0+a", "1.0.", Ordering::Less),
+        ("1.0~rc1", "1.0", Ordering::Less),
+        ("1.0~rc1", "1.0~rc2", Ordering::Less),
+        ("1.0~~", "1.0~", Ordering::Less),
+        ("1.0~~a", "1.0~", Ordering::Less),
+        ("1.0~", "1.0", Ordering::Less),
+        ("1.0~", "1.0a", Ordering::Less),
+        ("2.30-1", "2.30~rc1-1", Ordering::Greater),
+        ("1.0-1ubuntu1", "1.0-1", Ordering::Greater),
+        ("1.0-1ubuntu1", "1.0-2", Ordering::Less),
+        ("1.0-1+deb10u1", "1.0-1", Ordering::Greater),
+        ("1.2.3-4-5", "1.2.3-4", Ordering::Greater),
+    ];
+
+    for (a, b, expected) in cases {
+        let (a_, b_) = (Version::parse(a)?, Version::parse(b)?);
+        assert_eq!(a_.cmp(&b_), expected, "{a} <=> {b}");
+        assert_eq!(b_.cmp(&a_), expected.reverse(), "{b} <=> {a}");
+    }
+
+    let v = Version::parse("2:1.2-3-4ubuntu1")?;
+    assert_eq!(v.epoch, 2);
+    assert_eq!(v.upstream, "1.2-3");
+    assert_eq!(v.revision.as_deref(), Some("4ubuntu1"));
+    assert_eq!(Version::parse("0:1.0-1")?.to_string(), "1.0-1");
+
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    let cases = [
+        ("", ErrorKind::EmptyIdentifier(Part::Component), 0..0),
+        (":1.0", ErrorKind::EmptyIdentifier(Part::Epoch), 0..0),
+        ("a:1.0", ErrorKind::UnknownCharacter('a'), 0..1),
+        (
+            "99999999999999999999:1.0",
+            ErrorKind::Overflow(Part::Epoch),
+            0..20,
+        ),
+        ("1.0-", ErrorKind::EmptyIdentifier(Part::Revision), 4..4),
+        ("1:a1.0", ErrorKind::UnknownCharacter('a'), 2..3),
+        ("1.0_1", ErrorKind::UnknownCharacter('_'), 3..4),
+        ("1.0-1:2", ErrorKind::UnknownCharacter('.'), 1..2),
+    ];
+
+    for (input, kind, span) in cases {
+        assert_eq!(
+            Version::parse(input).map(|v| v.raw),
+            Err(Error::Parse(Diagnostic::new(kind, span, input))),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_semver() -> Result<()> {
+    let cases = [
+        ("1.2.3", "1.2.3"),
+        ("1.2", "1.2"),
+        ("1.2.3-rc.1", "1.2.3~rc.1"),
+        ("1.2.3.4", "1.2.3.4"),
+    ];
+    for (semver, debian) in cases {
+        let v = Version::from_semver(&Semver::parse(semver)?)?;
+        assert_eq!(v.to_string(), debian);
+        assert_eq!(v.to_semver()?.raw, Semver::parse(semver)?.raw);
+    }
+
+    assert!(Version::from_semver(&Semver::parse("1.2.3+build")?).is_err());
+    assert!(Version::from_semver(&Semver::parse("1.0.0-x-y")?).is_err());
+    for debian in ["1:1.0", "1.0-1", "1.0+dfsg", "1.00", "1.0~rc1~2"] {
+        assert!(Version::parse(debian)?.to_semver().is_err(), "{debian}");
+    }
+
+    // Conversion keeps SemVer 2.0 §11's order.
+    let sorted = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+        "1.0.1",
+    ];
+    let debian = sorted
+        .iter()
+        .map(|v| Version::from_semver(&Semver::parse(v)?))
+        .collect::<crate::Result<Vec<_>>>()?;
+    for pair in debian.windows(2) {
+        assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+    }
+
+    Ok(())
+}
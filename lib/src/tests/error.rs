@@ -0,0 +1,100 @@
+use crate::{range::Range, semver::Semver, Diagnostic, Error, ErrorKind, Part};
+
+fn diagnostic(e: Error) -> Diagnostic {
+    match e {
+        Error::Parse(d) => d,
+        e => panic!("expected a diagnostic, got {e:?}"),
+    }
+}
+
+#[test]
+fn test_semver_diagnostics() {
+    let cases = [
+        ("1.2.x", ErrorKind::UnknownCharacter('x'), 4..5),
+        ("v1..2", ErrorKind::EmptyIdentifier(Part::Component), 3..3),
+        ("", ErrorKind::EmptyIdentifier(Part::Component), 0..0),
+        ("1.2.3-", ErrorKind::EmptyIdentifier(Part::Prerelease), 6..6),
+        ("1.2.3-rc+", ErrorKind::EmptyIdentifier(Part::Build), 9..9),
+        ("1.2.3-rc_1", ErrorKind::UnknownCharacter('_'), 8..9),
+        ("1.2.3 ", ErrorKind::UnknownCharacter(' '), 5..6),
+        (
+            "1.99999999999999999999",
+            ErrorKind::Overflow(Part::Component),
+            2..22,
+        ),
+    ];
+
+    for (input, kind, span) in cases {
+        let d = diagnostic(Semver::parse(input).unwrap_err());
+        assert_eq!(d, Diagnostic::new(kind, span, input), "{input}");
+    }
+}
+
+#[test]
+fn test_range_diagnostics() {
+    let cases = [
+        ("^1.2,=>1.3", ErrorKind::BadOperator("=>".into()), 5..7),
+        ("^1||~>2", ErrorKind::BadOperator("~>".into()), 4..6),
+        ("^1,1.2", ErrorKind::BadOperator("".into()), 3..4),
+        ("^1,,^2", ErrorKind::EmptyConstraint, 3..3),
+        (">=3<2||^1", ErrorKind::InvertedBounds, 0..5),
+        ("^1, >=3 <2", ErrorKind::InvertedBounds, 4..10),
+        ("^1,~1.2.x", ErrorKind::UnknownCharacter('x'), 8..9),
+        (">=1.2.3-beta<2", ErrorKind::UnknownCharacter('-'), 7..8),
+        (">=1<2.x", ErrorKind::UnknownCharacter('x'), 6..7),
+        (">1<=2.x", ErrorKind::UnknownCharacter('x'), 6..7),
+        (">2<=1||^1", ErrorKind::InvertedBounds, 0..5),
+        (">1<=1", ErrorKind::InvertedBounds, 0..5),
+        ("^", ErrorKind::EmptyIdentifier(Part::Component), 1..1),
+        (
+            "^18446744073709551615",
+            ErrorKind::Overflow(Part::Component),
+            1..21,
+        ),
+        (
+            "^0.18446744073709551615",
+            ErrorKind::Overflow(Part::Component),
+            1..23,
+        ),
+        (
+            "~18446744073709551615",
+            ErrorKind::Overflow(Part::Component),
+            1..21,
+        ),
+        (
+            "^1,~1.18446744073709551615",
+            ErrorKind::Overflow(Part::Component),
+            4..26,
+        ),
+        (
+            "@18446744073709551615",
+            ErrorKind::Overflow(Part::Component),
+            1..21,
+        ),
+    ];
+
+    for (input, kind, span) in cases {
+        let d = diagnostic(Range::parse(input).unwrap_err());
+        assert_eq!(d, Diagnostic::new(kind, span, input), "{input}");
+    }
+}
+
+#[test]
+fn test_render() {
+    let e = Range::parse(">=1<2||^1.02.x").unwrap_err();
+
+    assert_eq!(
+        e.to_string(),
+        "unexpected character 'x' at byte 13 of '>=1<2||^1.02.x'"
+    );
+    assert_eq!(
+        e.render(),
+        "unexpected character 'x'\n  >=1<2||^1.02.x\n               ^"
+    );
+
+    let e = Semver::parse_strict("1.02.3").unwrap_err();
+    assert_eq!(e.render(), "leading zero in minor\n  1.02.3\n    ^^");
+
+    let e = Error::Range("no constraints".into());
+    assert_eq!(e.render(), "invalid range: no constraints");
+}
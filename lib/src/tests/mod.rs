@@ -1,2 +1,9 @@
+mod calver;
+mod cargo;
+mod debian;
+mod error;
+mod npm;
+mod pep440;
 mod range;
+mod rpm;
 mod semver;
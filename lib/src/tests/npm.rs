@@ -0,0 +1,305 @@
+use crate::{
+    range::{dialect::npm, Range},
+    semver::Semver,
+    Diagnostic, Error, ErrorKind,
+};
+use anyhow::Result;
+
+// Desugarings from node-semver's `test/fixtures/range-parse.js`, written as
+// equivalent pkgx ranges.
+#[test]
+fn test_parse() -> Result<()> {
+    let cases = [
+        ("1.0.0 - 2.0.0", ">=1<2,=2"),
+        ("1.0.0 - 2.0.0 ", ">=1<2,=2"),
+        ("1 - 2", ">=1<3"),
+        ("1.0 - 2.0", ">=1<2.1"),
+        ("1.2 - 3.4.5", ">=1.2<3.4.5,=3.4.5"),
+        ("1.2.3 - 3.4", ">=1.2.3<3.5"),
+        ("1.2.3 - 2", ">=1.2.3<3"),
+        ("* - 2", "<3"),
+        ("1.2.3 - *", ">=1.2.3"),
+        ("1.0.0", "=1.0.0"),
+        ("=1.0.0", "=1.0.0"),
+        ("v1.0.0", "=1.0.0"),
+        (">=*", "*"),
+        ("", "*"),
+        ("*", "*"),
+        ("x", "*"),
+        ("X || x", "*"),
+        (">=1.0.0", ">=1"),
+        (">1.0", ">=1.1"),
+        (">1", ">=2"),
+        ("<=2.0.0", "<2,=2"),
+        ("<=2.0", "<2.1"),
+        ("<2.0.0", "<2"),
+        ("< 2.0.0", "<2"),
+        (">= 1.0.0", ">=1"),
+        (">=  1.0.0", ">=1"),
+        ("<=   2.0.0", "<2,=2"),
+        ("0.1.20 || 1.2.4", "=0.1.20,=1.2.4"),
+        (">=0.2.3 || <0.0.1", ">=0.2.3,<0.0.1"),
+        ("||", "*"),
+        ("2.x.x", "^2"),
+        ("1.2.x", "~1.2"),
+        ("1.2.x || 2.x", "~1.2,^2"),
+        ("1.2.*", "~1.2"),
+        ("2.*.*", "^2"),
+        ("1", "^1"),
+        ("2.3", "~2.3"),
+        ("~2.4", "~2.4"),
+        ("~>3.2.1", ">=3.2.1<3.3"),
+        ("~1", "^1"),
+        ("~>1", "^1"),
+        ("~> 1", "^1"),
+        ("~1.0", "~1.0"),
+        ("~ 1.0", "~1.0"),
+        ("^0", "^0"),
+        ("^ 1", "^1"),
+        ("^0.1", "~0.1"),
+        ("^1.0", "^1"),
+        ("^1.2", "^1.2"),
+        ("^0.0.1", "=0.0.1,>=0.0.1<0.0.2"),
+        ("^0.1.2", ">=0.1.2<0.2"),
+        ("^1.2.3", ">=1.2.3<2"),
+        ("^0.0.x", "~0.0"),
+        ("^0.0", "~0.0"),
+        ("^0.x", "^0"),
+        ("<1", "<1"),
+        ("< 1", "<1"),
+        (">=1", ">=1"),
+        (">= 1", ">=1"),
+        ("<1.2", "<1.2"),
+        ("< 1.2", "<1.2"),
+        (">=1.2.1 1.2.3", "=1.2.3"),
+        ("1.2.3 >=1.2.1", "=1.2.3"),
+        (">=1.2.3 >=1.2.1", ">=1.2.3"),
+        (">=1.2.1 >=1.2.3", ">=1.2.3"),
+        (">=1.2", ">=1.2"),
+        (">=1.2.3 <2", ">=1.2.3<2"),
+        ("^1.2 <1.5 || >=3.1 <=3.1.4", ">=1.2<1.5,>=3.1<3.1.4,=3.1.4"),
+    ];
+
+    for (npm, pkgx) in cases {
+        let got = npm::parse(npm)?;
+        let want = Range::parse(pkgx)?;
+        assert!(got.equivalent(&want), "{npm}: got {got}, want {want}");
+    }
+
+    // Strict lower bounds on full versions stay strict.
+    assert_eq!(npm::parse(">1.2.3")?.raw, ">1.2.3");
+
+    Ok(())
+}
+
+// Contradictory comparator sets match nothing.
+#[test]
+fn test_parse_empty() -> Result<()> {
+    for npm in [">1 <1", "<0.0.0", ">*", "<x", "1.x 2.x", "<1.2.3 >=1.2.3"] {
+        assert!(npm::parse(npm)?.is_empty(), "{npm}");
+    }
+    assert!(!npm::parse(">1 <1 || 3")?.is_empty());
+
+    Ok(())
+}
+
+// From node-semver's `test/fixtures/range-include.js` and
+// `range-exclude.js`, leaving out loose mode and prerelease filtering.
+#[test]
+fn test_satisfies() -> Result<()> {
+    let cases = [
+        ("1.0.0 - 2.0.0", "1.2.3", true),
+        ("^1.2.3+build", "1.2.3", true),
+        ("^1.2.3+build", "1.3.0", true),
+        ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3", true),
+        ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "2.4.3-alpha", true),
+        ("1.2.3+asdf - 2.4.3+asdf", "1.2.3", true),
+        ("1.0.0", "1.0.0", true),
+        (">=*", "0.2.4", true),
+        ("", "1.0.0", true),
+        ("*", "1.2.3", true),
+        (">=1.0.0", "1.0.0", true),
+        (">=1.0.0", "1.0.1", true),
+        (">=1.0.0", "1.1.0", true),
+        ("<=2.0.0", "2.0.0", true),
+        ("<=2.0.0", "1.9999.9999", true),
+        ("<=2.0.0", "0.2.9", true),
+        ("<2.0.0", "1.9999.9999", true),
+        ("<2.0.0", "0.2.9", true),
+        (">= 1.0.0", "1.0.0", true),
+        ("<=  2.0.0", "2.0.0", true),
+        ("0.1.20 || 1.2.4", "1.2.4", true),
+        (">=0.2.3 || <0.0.1", "0.0.0", true),
+        (">=0.2.3 || <0.0.1", "0.2.3", true),
+        (">=0.2.3 || <0.0.1", "0.2.4", true),
+        ("||", "1.3.4", true),
+        ("2.x.x", "2.1.3", true),
+        ("1.2.x", "1.2.3", true),
+        ("1.2.x || 2.x", "2.1.3", true),
+        ("1.2.x || 2.x", "1.2.3", true),
+        ("x", "1.2.3", true),
+        ("2.*.*", "2.1.3", true),
+        ("1.2.*", "1.2.3", true),
+        ("2", "2.1.2", true),
+        ("2.3", "2.3.1", true),
+        ("~0.0.1", "0.0.1", true),
+        ("~0.0.1", "0.0.2", true),
+        ("~x", "0.0.9", true),
+        ("~2", "2.0.9", true),
+        ("~2.4", "2.4.0", true),
+        ("~2.4", "2.4.5", true),
+        ("~>3.2.1", "3.2.2", true),
+        ("~1", "1.2.3", true),
+        ("~>1", "1.2.3", true),
+        ("~> 1", "1.2.3", true),
+        ("~1.0", "1.0.2", true),
+        ("~ 1.0", "1.0.2", true),
+        ("~ 1.0.3", "1.0.12", true),
+        (">=1", "1.0.0", true),
+        (">= 1", "1.0.0", true),
+        ("<1.2", "1.1.1", true),
+        ("< 1.2", "1.1.1", true),
+        ("~v0.5.4-pre", "0.5.5", true),
+        ("~v0.5.4-pre", "0.5.4", true),
+        ("=0.7.x", "0.7.2", true),
+        ("<=0.7.x", "0.7.2", true),
+        (">=0.7.x", "0.7.2", true),
+        ("<=0.7.x", "0.6.2", true),
+        ("~1.2.1 >=1.2.3", "1.2.3", true),
+        ("~1.2.1 =1.2.3", "1.2.3", true),
+        ("~1.2.1 1.2.3", "1.2.3", true),
+        ("~1.2.1 >=1.2.3 1.2.3", "1.2.3", true),
+        ("~1.2.1 1.2.3 >=1.2.3", "1.2.3", true),
+        (">=1.2.1 1.2.3", "1.2.3", true),
+        ("1.2.3 >=1.2.1", "1.2.3", true),
+        (">=1.2.3 >=1.2.1", "1.2.3", true),
+        (">=1.2.1 >=1.2.3", "1.2.3", true),
+        (">=1.2", "1.2.8", true),
+        ("^1.2.3", "1.8.1", true),
+        ("^0.1.2", "0.1.2", true),
+        ("^0.1", "0.1.2", true),
+        ("^0.0.1", "0.0.1", true),
+        ("^1.2", "1.4.2", true),
+        ("^1.2 ^1", "1.4.2", true),
+        ("^1.2.3-alpha", "1.2.3-pre", true),
+        ("^1.2.0-alpha", "1.2.0-pre", true),
+        ("^0.0.1-alpha", "0.0.1-beta", true),
+        ("^0.0.1-alpha", "0.0.1", true),
+        ("^0.1.1-alpha", "0.1.1-beta", true),
+        ("^x", "1.2.3", true),
+        ("x - 1.0.0", "0.9.7", true),
+        ("x - 1.x", "0.9.7", true),
+        ("1.0.0 - x", "1.9.7", true),
+        ("1.x - x", "1.9.7", true),
+        ("<=7.x", "7.9.9", true),
+        ("1.0.0 - 2.0.0", "2.2.3", false),
+        ("1.2.3+asdf - 2.4.3+asdf", "1.2.3-pre.2", false),
+        ("^1.2.3+build", "2.0.0", false),
+        ("^1.2.3+build", "1.2.0", false),
+        ("^1.2.3", "1.2.3-pre", false),
+        ("^1.2", "1.2.0-pre", false),
+        ("1.0.0", "1.0.1", false),
+        (">=1.0.0", "0.0.0", false),
+        (">=1.0.0", "0.0.1", false),
+        (">=1.0.0", "0.1.0", false),
+        ("<=2.0.0", "3.0.0", false),
+        ("<=2.0.0", "2.9999.9999", false),
+        ("<=2.0.0", "2.2.9", false),
+        ("<2.0.0", "2.9999.9999", false),
+        ("<2.0.0", "2.2.9", false),
+        (">=0.1.97", "0.1.93", false),
+        ("0.1.20 || 1.2.4", "1.2.3", false),
+        (">=0.2.3 || <0.0.1", "0.0.3", false),
+        (">=0.2.3 || <0.0.1", "0.2.2", false),
+        ("2.x.x", "1.1.3", false),
+        ("2.x.x", "3.1.3", false),
+        ("1.2.x", "1.3.3", false),
+        ("1.2.x || 2.x", "3.1.3", false),
+        ("1.2.x || 2.x", "1.1.3", false),
+        ("2.*.*", "1.1.3", false),
+        ("2.*.*", "3.1.3", false),
+        ("1.2.*", "1.3.3", false),
+        ("2", "1.1.2", false),
+        ("2.3", "2.4.1", false),
+        ("~0.0.1", "0.1.0", false),
+        ("~2.4", "2.5.0", false),
+        ("~2.4", "2.3.9", false),
+        ("~>3.2.1", "3.3.2", false),
+        ("~>3.2.1", "3.2.0", false),
+        ("~1", "0.2.3", false),
+        ("~>1", "2.2.3", false),
+        ("~1.0", "1.1.0", false),
+        ("<1", "1.0.0", false),
+        (">=1.2", "1.1.1", false),
+        ("=0.7.x", "0.8.2", false),
+        (">=0.7.x", "0.6.2", false),
+        ("<0.7.x", "0.7.2", false),
+        ("1.0.0 - 2.0.0", "2.2.3", false),
+        ("1", "2.0.0", false),
+        ("^1.2.3", "2.0.0", false),
+        ("^1.2.3", "1.2.2", false),
+        ("^1.2", "1.1.9", false),
+        ("*", "1.2.3-foo", true),
+        ("^0.1.0", "0.2.0", false),
+        ("^0.0.1", "0.0.2", false),
+        ("<0.0.0", "0.0.0", false),
+        ("x - 1.0.0", "1.0.1", false),
+        ("1.x - x", "0.9.7", false),
+        ("1.0.0 - x", "0.9.7", false),
+    ];
+
+    for (npm, version, expected) in cases {
+        let range = npm::parse(npm)?;
+        let semver = Semver::parse(version)?;
+        assert_eq!(
+            range.satisfies(&semver),
+            expected,
+            "{npm} satisfies {version}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    let cases = [
+        (">=1.2.3 =>2", ErrorKind::BadOperator("=>".into()), 8..10),
+        ("^1.2 || 1.2.3.4", ErrorKind::UnknownCharacter('.'), 13..14),
+        ("1.x-beta", ErrorKind::UnknownCharacter('-'), 3..4),
+        (">=1.y", ErrorKind::UnknownCharacter('y'), 4..5),
+        (
+            "^",
+            ErrorKind::EmptyIdentifier(crate::Part::Component),
+            1..1,
+        ),
+        (
+            "^18446744073709551615",
+            ErrorKind::Overflow(crate::Part::Component),
+            1..21,
+        ),
+        (
+            "~1.18446744073709551615",
+            ErrorKind::Overflow(crate::Part::Component),
+            1..23,
+        ),
+        (
+            "<=1.18446744073709551615",
+            ErrorKind::Overflow(crate::Part::Component),
+            2..24,
+        ),
+        (
+            "18446744073709551615.x",
+            ErrorKind::Overflow(crate::Part::Component),
+            0..22,
+        ),
+    ];
+
+    for (input, kind, span) in cases {
+        assert_eq!(
+            npm::parse(input),
+            Err(Error::Parse(Diagnostic::new(kind, span, input))),
+            "{input}"
+        );
+    }
+}
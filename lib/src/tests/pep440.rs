@@ -0,0 +1,244 @@
+use crate::{
+    range::dialect::pep440::SpecifierSet,
+    semver::dialect::pep440::{Phase, Version},
+    Diagnostic, Error, ErrorKind, Part,
+};
+use anyhow::Result;
+
+#[test]
+fn test_parse() -> Result<()> {
+    let v = Version::parse("1!2.0rc1.post3.dev4+ubuntu.1")?;
+    assert_eq!(v.epoch, 1);
+    assert_eq!(v.release, vec![2, 0]);
+    assert_eq!(v.pre, Some((Phase::Rc, 1)));
+    assert_eq!(v.post, Some(3));
+    assert_eq!(v.dev, Some(4));
+    assert_eq!(v.local, vec!["ubuntu", "1"]);
+    assert!(v.is_prerelease());
+    assert!(v.is_postrelease());
+
+    // Alternate spellings normalise.
+    let cases = [
+        ("1.0", "1.0"),
+        ("v1.0", "1.0"),
+        (" 1.0\n", "1.0"),
+        ("1.0rc1", "1.0rc1"),
+        ("1.0-RC1", "1.0rc1"),
+        ("1.0.c1", "1.0rc1"),
+        ("1.0pre1", "1.0rc1"),
+        ("1.0preview_1", "1.0rc1"),
+        ("1.0alpha", "1.0a0"),
+        ("1.0.beta.2", "1.0b2"),
+        ("2.0.post3", "2.0.post3"),
+        ("2.0-3", "2.0.post3"),
+        ("2.0-r", "2.0.post0"),
+        ("2.0rev4", "2.0.post4"),
+        ("1.0.dev4", "1.0.dev4"),
+        ("1.0-dev", "1.0.dev0"),
+        ("1.0_a1_post2_dev3", "1.0a1.post2.dev3"),
+        ("1!2.0", "1!2.0"),
+        ("0!2.0", "2.0"),
+        ("1.0+Ubuntu-1_2", "1.0+ubuntu.1.2"),
+    ];
+    for (input, normalised) in cases {
+        assert_eq!(Version::parse(input)?.to_string(), normalised, "{input}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_errors() {
+    let cases = [
+        ("", ErrorKind::EmptyIdentifier(Part::Component), 0..0),
+        ("1.0.", ErrorKind::UnknownCharacter('.'), 3..4),
+        ("1.0gamma", ErrorKind::UnknownCharacter('g'), 3..4),
+        ("1.0+", ErrorKind::UnknownCharacter('+'), 3..4),
+        ("1.0 rc1", ErrorKind::UnknownCharacter(' '), 3..4),
+        ("1!", ErrorKind::UnknownCharacter('!'), 1..2),
+        (
+            "1.99999999999999999999",
+            ErrorKind::Overflow(Part::Component),
+            2..22,
+        ),
+    ];
+
+    for (input, kind, span) in cases {
+        assert_eq!(
+            Version::parse(input).map(|v| v.raw),
+            Err(Error::Parse(Diagnostic::new(kind, span, input))),
+            "{input}"
+        );
+    }
+}
+
+// The example ordering from PEP 440's "Summary of permitted suffixes and
+// relative ordering", plus epochs and local labels.
+#[test]
+fn test_ordering() -> Result<()> {
+    let sorted = [
+        "1.dev0",
+        "1.0.dev456",
+        "1.0a1",
+        "1.0a2.dev456",
+        "1.0a12.dev456",
+        "1.0a12",
+        "1.0b1.dev456",
+        "1.0b2",
+        "1.0b2.post345.dev456",
+        "1.0b2.post345",
+        "1.0rc1.dev456",
+        "1.0rc1",
+        "1.0",
+        "1.0+abc.5",
+        "1.0+abc.7",
+        "1.0+5",
+        "1.0.post456.dev34",
+        "1.0.post456",
+        "1.0.15",
+        "1.1.dev1",
+        "2013.10",
+        "1!0.1",
+    ];
+    let versions = sorted
+        .iter()
+        .map(|v| Version::parse(v))
+        .collect::<crate::Result<Vec<_>>>()?;
+    for pair in versions.windows(2) {
+        assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+    }
+
+    assert_eq!(Version::parse("1.0")?, Version::parse("1.0.0")?);
+    assert_eq!(Version::parse("1.0RC1")?, Version::parse("1.0rc1")?);
+    assert_ne!(Version::parse("1.0")?, Version::parse("1.0+local")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_specifiers() -> Result<()> {
+    let cases = [
+        ("~=1.4", "1.4", true),
+        ("~=1.4", "1.9.2", true),
+        ("~=1.4", "2.0", false),
+        ("~=1.4", "1.3", false),
+        ("~=1.4.5", "1.4.9", true),
+        ("~=1.4.5", "1.5.0", false),
+        ("~=1.4.5a4", "1.4.5", true),
+        ("~=1.4.5a4", "1.4.5a5", true),
+        ("==1.5.*", "1.5.3", true),
+        ("==1.5.*", "1.5", true),
+        ("==1.5.*", "1.6", false),
+        ("!=1.5.*", "1.5.3", false),
+        ("!=1.5.*", "1.6", true),
+        ("==1.0", "1.0.0", true),
+        ("==1.0", "1.0+local", true),
+        ("==1.0+local", "1.0", false),
+        ("!=1.0", "1.0.1", true),
+        (">=1.0,<2", "1.5", true),
+        (">=1.0,<2", "2.0", false),
+        (">=1.0, <2, !=1.5.*", "1.5.1", false),
+        ("<=2.0", "2.0+local", true),
+        ("<2.0", "2.0rc1", false),
+        ("<2.0rc2", "2.0rc1", true),
+        ("<2.0", "1.9", true),
+        (">1.7", "1.7.1", true),
+        (">1.7", "1.7.post2", false),
+        (">1.7.post2", "1.7.post3", true),
+        (">1.7", "1.7+local", false),
+        (">1.7", "1.8+local", true),
+        ("===1.0", "1.0", true),
+        ("===1.0", "1.0.0", false),
+        (">=2", "1!1.0", true),
+        ("<2", "1!1.0", false),
+        (">=1!1.0", "1!1.0", true),
+        ("", "1.0", true),
+        // Pre-releases only match when asked for.
+        (">=1.0", "2.0b1", false),
+        (">=1.0b1", "2.0b1", true),
+        ("==2.0.dev3", "2.0.dev3", true),
+        ("", "2.0a1", false),
+    ];
+
+    for (specifiers, version, expected) in cases {
+        let set = SpecifierSet::parse(specifiers)?;
+        assert_eq!(
+            set.satisfies(&Version::parse(version)?),
+            expected,
+            "{specifiers} satisfies {version}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_specifier_errors() {
+    let cases = [
+        ("1.0", ErrorKind::BadOperator("".into()), 0..0),
+        (">=1.0, =>2", ErrorKind::BadOperator("=>".into()), 7..9),
+        (">=1.0,", ErrorKind::EmptyConstraint, 6..6),
+        (">=1.0, <2.x", ErrorKind::UnknownCharacter('.'), 9..10),
+    ];
+    for (input, kind, span) in cases {
+        assert_eq!(
+            SpecifierSet::parse(input).map(|s| s.raw),
+            Err(Error::Parse(Diagnostic::new(kind, span, input))),
+            "{input}"
+        );
+    }
+
+    for input in ["~=1", ">=1.0.*", "==1.0rc1.*", "<1.0+local"] {
+        assert!(
+            matches!(SpecifierSet::parse(input), Err(Error::Range(_))),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_max() -> Result<()> {
+    let versions = ["1.0", "1.5", "2.0rc1", "2.0.dev1"]
+        .iter()
+        .map(|v| Version::parse(v))
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let max = |s: &str| -> Result<Option<String>> {
+        Ok(SpecifierSet::parse(s)?
+            .max(&versions)
+            .map(|v| v.to_string()))
+    };
+    assert_eq!(max(">=1.0")?, Some("1.5".to_string()));
+    assert_eq!(max(">=2.0rc1")?, Some("2.0rc1".to_string()));
+    // Only pre-releases match, so they're allowed.
+    assert_eq!(max(">=1.6")?, Some("2.0rc1".to_string()));
+    assert_eq!(max(">=3")?, None);
+
+    let set = SpecifierSet::parse(">=1.0")?;
+    assert_eq!(
+        set.min(&versions).map(|v| v.to_string()),
+        Some("1.0".into())
+    );
+    let filtered = |s: &str| -> Result<Vec<String>> {
+        Ok(SpecifierSet::parse(s)?
+            .filter(&versions)
+            .iter()
+            .map(|v| v.to_string())
+            .collect())
+    };
+    assert_eq!(filtered(">1.0")?, ["1.5"]);
+    assert_eq!(filtered(">=1.6")?, ["2.0rc1", "2.0.dev1"]);
+    assert_eq!(
+        SpecifierSet::parse(">=1.6")?
+            .min(&versions)
+            .map(|v| v.to_string()),
+        Some("2.0.dev1".into())
+    );
+
+    assert_eq!(
+        SpecifierSet::parse(" >= 1.0 ,!=1.5.* ")?.to_string(),
+        ">=1.0,!=1.5.*"
+    );
+
+    Ok(())
+}
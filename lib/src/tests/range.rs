@@ -1,4 +1,10 @@
-use crate::{range::Range, semver::Semver};
+use crate::{
+    range::{
+        satisfies::{PrereleasePolicy, SatisfyOptions},
+        Constraint, Range,
+    },
+    semver::Semver,
+};
 use anyhow::Result;
 #[cfg(feature = "serde")]
 use serde_test::{assert_tokens, Token};
@@ -32,7 +38,7 @@ fn test_parse() -> Result<()> {
     assert!(h.is_err());
     assert!(i.is_err());
     assert!(j.is_err());
-    assert!(k.is_err());
+    assert!(k?.is_empty());
     assert!(l.is_ok());
     assert!(m.is_ok());
     assert!(n.is_ok());
@@ -109,6 +115,66 @@ fn test_satisfies() -> Result<()> {
     assert!(ri.satisfies(&sj));
     assert!(!ri.satisfies(&sk));
 
+    let rk = Range::parse("^1.0.0-rc.2")?;
+
+    assert!(rk.satisfies(&Semver::parse("1.0.0-rc.10")?));
+    assert!(!rk.satisfies(&Semver::parse("1.0.0-rc.1")?));
+
+    Ok(())
+}
+
+#[test]
+fn test_satisfies_prerelease() -> Result<()> {
+    let options = |include_prerelease| SatisfyOptions { include_prerelease };
+    let (never, same, always) = (
+        options(PrereleasePolicy::Never),
+        options(PrereleasePolicy::SameTuple),
+        options(PrereleasePolicy::Always),
+    );
+
+    let ra = Range::parse("^1.2")?;
+    let rb = Range::parse("^1.2.3-beta")?;
+    let rc = Range::parse("=1.2.3-beta,^2")?;
+    let rd = Range::parse("!=1.5.0")?;
+    let sa = Semver::parse("1.3.0-beta")?;
+    let sb = Semver::parse("1.2.3-rc")?;
+    let sc = Semver::parse("1.3.0")?;
+
+    // pkgx's behavior stays the default.
+    assert_eq!(SatisfyOptions::default(), always);
+    assert!(ra.satisfies(&sa));
+    assert!(ra.satisfies_with(&sa, &always));
+    assert!(!ra.satisfies_with(&sa, &same));
+    assert!(!ra.satisfies_with(&sa, &never));
+
+    assert!(rb.satisfies_with(&sb, &same));
+    assert!(!rb.satisfies_with(&sa, &same));
+    assert!(!rb.satisfies_with(&sb, &never));
+    assert!(rb.satisfies_with(&sc, &never));
+
+    // Only the constraint the version falls in counts.
+    assert!(!rc.satisfies_with(&Semver::parse("2.0.0-alpha")?, &same));
+    assert!(!rd.satisfies_with(&sa, &same));
+    assert!(rd.satisfies_with(&sa, &always));
+
+    let versions = [sa.clone(), sc.clone(), Semver::parse("1.4.0-rc.1")?];
+    assert_eq!(ra.max(&versions).map(|v| v.raw), Some("1.4.0-rc.1".into()));
+    assert_eq!(ra.max_with(&versions, &same), Some(sc.clone()));
+    assert_eq!(ra.min(&versions), Some(sa.clone()));
+    assert_eq!(ra.min_with(&versions, &same), Some(sc.clone()));
+    assert_eq!(
+        ra.filter_with(&versions, &same).collect::<Vec<_>>(),
+        vec![&sc]
+    );
+    assert_eq!(ra.filter_with(&versions[..1], &never).count(), 0);
+    assert_eq!(ra.max_with(&[sa], &never), None);
+
+    assert_eq!(
+        PrereleasePolicy::parse("same-tuple")?,
+        PrereleasePolicy::SameTuple
+    );
+    assert!(PrereleasePolicy::parse("sometimes").is_err());
+
     Ok(())
 }
 
@@ -137,6 +203,71 @@ fn test_max() -> Result<()> {
     assert_eq!(rb.max(&sa).unwrap().raw, "14.5.0");
     assert_eq!(rc.max(&sa).unwrap().raw, "16.8.0");
     assert!(rd.max(&sa).is_none());
+
+    let re = Range::parse("^1.0.0-rc.1")?;
+    let sb = vec![
+        Semver::parse("1.0.0-rc.2")?,
+        Semver::parse("1.0.0-rc.10")?,
+        Semver::parse("1.0.0-rc.9")?,
+    ];
+
+    assert_eq!(re.max(&sb).unwrap().raw, "1.0.0-rc.10");
+
+    // 0.0.0 is a real candidate, not a sentinel.
+    let rf = Range::parse("<1")?;
+    let sc = [Semver::parse("0.0.0")?, Semver::parse("1.0.0")?];
+    assert_eq!(rf.max(&sc).unwrap().raw, "0.0.0");
+    assert_eq!(rf.max(sc.iter().rev()).unwrap().raw, "0.0.0");
+    assert!(rf.max(&[]).is_none());
+    Ok(())
+}
+
+#[test]
+fn test_min() -> Result<()> {
+    let ra = Range::parse("*")?;
+    let rb = Range::parse(">=11<15")?;
+    let rc = Range::parse("^3")?;
+
+    let sa = vec![
+        Semver::parse("12.5")?,
+        Semver::parse("11.5")?,
+        Semver::parse("16.3")?,
+        Semver::parse("0.0.0")?,
+        Semver::parse("14.5")?,
+    ];
+
+    assert_eq!(ra.min(&sa).unwrap().raw, "0.0.0");
+    assert_eq!(rb.min(&sa).unwrap().raw, "11.5.0");
+    assert!(rc.min(&sa).is_none());
+    assert_eq!(rb.min(sa.iter().take(1)).unwrap().raw, "12.5.0");
+
+    Ok(())
+}
+
+#[test]
+fn test_filter() -> Result<()> {
+    let ra = Range::parse("^1,=3")?;
+
+    let sa = vec![
+        Semver::parse("1.5")?,
+        Semver::parse("2.0")?,
+        Semver::parse("3.0")?,
+        Semver::parse("1.2")?,
+        Semver::parse("0.9")?,
+    ];
+
+    let filtered = ra.filter(&sa).map(|s| s.raw.as_str()).collect::<Vec<_>>();
+    assert_eq!(filtered, ["1.5.0", "3.0.0", "1.2.0"]);
+
+    let sorted = ra.sorted_matches(&sa);
+    assert_eq!(
+        sorted.iter().map(|s| s.raw.as_str()).collect::<Vec<_>>(),
+        ["1.2.0", "1.5.0", "3.0.0"]
+    );
+
+    assert_eq!(Range::none().filter(&sa).count(), 0);
+    assert!(Range::none().sorted_matches(&sa).is_empty());
+
     Ok(())
 }
 
@@ -159,13 +290,14 @@ fn test_intersect() -> Result<()> {
 
     let ic = ra.intersect(&rd);
     assert!(ic.is_ok());
-    assert_eq!(ic?.raw, "^3.7");
+    assert_eq!(ic?.to_string(), "^3.7");
 
     let re = Range::parse("~3.7")?;
     let rf = Range::parse("~3.8")?;
 
     let id = re.intersect(&rf);
-    assert!(id.is_err());
+    assert!(id.is_ok());
+    assert!(id?.is_empty());
 
     let rg = Range::parse("=3.8.0")?;
 
@@ -190,11 +322,11 @@ fn test_intersect() -> Result<()> {
 
     let ih = rk.intersect(&ra);
     assert!(ih.is_ok());
-    assert_eq!(ih?.raw, ra.raw);
+    assert_eq!(ih?, ra);
 
     let ii = ra.intersect(&rk);
     assert!(ii.is_ok());
-    assert_eq!(ii?.raw, ra.raw);
+    assert_eq!(ii?, ra);
 
     let rl = Range::parse("=3.8.0")?;
     let rm = Range::parse("=3.9.0")?;
@@ -203,7 +335,7 @@ fn test_intersect() -> Result<()> {
     let ik = rg.intersect(&rm);
 
     assert!(ij.is_ok());
-    assert!(ik.is_err());
+    assert!(ik?.is_empty());
     assert_eq!(ij?.raw, rl.raw);
 
     let il = rg.intersect(&ra);
@@ -225,6 +357,269 @@ fn test_intersect() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_intersect_any() -> Result<()> {
+    // `*` among other constraints is just another interval.
+    let ra = Range {
+        raw: "^1,*".into(),
+        set: vec![Constraint::parse("^1")?, Constraint::Any],
+    };
+    let rb = Range::parse("~2.3,=4")?;
+
+    assert_eq!(ra.intersect(&rb)?, rb);
+    assert_eq!(rb.intersect(&ra)?, rb);
+    assert_eq!(ra.intersect(&ra)?, Range::any());
+
+    // The result never comes from re-parsing `raw`, even when it's stale.
+    let rc = Range {
+        raw: "not a range".into(),
+        set: vec![Constraint::parse("^1.2")?],
+    };
+    assert_eq!(Range::any().intersect(&rc)?, rc);
+    assert_eq!(rc.intersect(&Range::any())?.raw, ">=1.2.0<2.0.0");
+
+    Ok(())
+}
+
+#[test]
+fn test_intersect_all() -> Result<()> {
+    let ranges = [
+        Range::parse("^1")?,
+        Range::parse(">=1.2")?,
+        Range::parse("~1.4,~1.6")?,
+        Range::parse("<1.6.5")?,
+    ];
+
+    assert_eq!(
+        Range::intersect_all(&ranges).raw,
+        ">=1.4.0<1.5.0,>=1.6.0<1.6.5"
+    );
+    assert_eq!(Range::intersect_all(&ranges[..1]), ranges[0]);
+    assert_eq!(Range::intersect_all(&[]), Range::any());
+
+    let disjoint = [Range::parse("^1")?, Range::parse("^2")?, Range::any()];
+    assert!(Range::intersect_all(&disjoint).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_none() -> Result<()> {
+    let ra = Range::none();
+    let rb = Range::parse("<0")?;
+    let rc = Range::parse("")?;
+    let rd = Range::parse("~1.2")?.intersect(&Range::parse("~1.3")?)?;
+
+    for r in [&ra, &rb, &rc, &rd] {
+        assert!(r.is_empty());
+        assert!(r.set.is_empty());
+        assert_eq!(r.raw, "<0");
+        assert_eq!(r.to_string(), "<0");
+        assert!(!r.satisfies(&Semver::parse("0.0.0")?));
+        assert!(r.max(&[Semver::parse("1.2.3")?]).is_none());
+    }
+    assert_eq!(ra.raw(), "<0");
+    assert!(!Range::any().is_empty());
+    assert!(!Range::parse("=0")?.is_empty());
+
+    // Empty flows through the other set operations.
+    let re = Range::parse("^1")?;
+    assert_eq!(re.intersect(&ra)?, ra);
+    assert_eq!(ra.intersect(&re)?, ra);
+    assert_eq!(re.union(&ra), re);
+    assert_eq!(ra.complement(), Range::any());
+    assert!(ra.is_subset_of(&re));
+    assert!(ra.is_disjoint(&Range::any()));
+
+    // Malformed input is still an error.
+    assert!(Range::parse("<").is_err());
+    assert!(Range::parse(",").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_union() -> Result<()> {
+    let ra = Range::parse("^1")?;
+    let rb = Range::parse("^2")?;
+    let rc = Range::parse("~1.2,=3.1")?;
+    let rd = Range::parse("=1.5")?;
+
+    assert_eq!(ra.union(&rb).raw, ">=1.0.0<3.0.0");
+    assert_eq!(ra.union(&rc).raw, ">=1.0.0<2.0.0,=3.1.0");
+    assert_eq!(rc.union(&rd).raw, ">=1.2.0<1.3.0,=1.5.0,=3.1.0");
+    assert_eq!(ra.union(&rd), ra);
+    assert_eq!(ra.union(&Range::any()), Range::any());
+    assert_eq!(
+        Range::parse("<1")?.union(&Range::parse(">=1")?),
+        Range::any()
+    );
+
+    // Adjacent singles close the gap left by an exclusive upper bound.
+    let re = Range::parse(">=1<2")?.union(&Range::parse("=2")?);
+    assert_eq!(re.raw, ">=1.0.0<=2.0.0");
+    assert_eq!(re.union(&Range::parse(">=2<3")?).raw, ">=1.0.0<3.0.0");
+
+    Ok(())
+}
+
+#[test]
+fn test_difference() -> Result<()> {
+    let ra = Range::parse("^1")?;
+    let rb = Range::parse("~1.2")?;
+    let rc = Range::parse(">=1.5")?;
+
+    assert_eq!(ra.difference(&rb).raw, ">=1.0.0<1.2.0,>=1.3.0<2.0.0");
+    assert_eq!(ra.difference(&rc).raw, ">=1.0.0<1.5.0");
+    assert_eq!(rb.difference(&ra), Range::none());
+    assert_eq!(ra.difference(&Range::parse("^3")?), ra);
+    assert_eq!(Range::any().difference(&ra).raw, ">=0.0.0<1.0.0,>=2.0.0");
+
+    // Removing a single version from inside an interval excludes it.
+    assert_eq!(
+        ra.difference(&Range::parse("=1.2.3")?).raw,
+        ">=1.0.0<2.0.0,!=1.2.3"
+    );
+    assert_eq!(
+        Range::parse("=1,=2")?.difference(&Range::parse("=1")?).raw,
+        "=2.0.0"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_complement() -> Result<()> {
+    let ra = Range::parse("^1")?;
+    let rb = Range::parse("<1")?;
+    let rc = Range::parse(">=1.5")?;
+
+    assert_eq!(ra.complement().to_string(), ">=0<1,>=2");
+    assert_eq!(rb.complement().to_string(), ">=1");
+    assert_eq!(rc.complement().to_string(), ">=0<1.5");
+    assert!(Range::any().complement().is_empty());
+    assert_eq!(ra.complement().complement(), ra);
+
+    let rd = Range::parse("=1")?.complement();
+    assert_eq!(rd.raw, "!=1.0.0");
+    assert_eq!(Range::parse(&rd.to_string())?, rd);
+    assert_eq!(Range::parse("=1")?.complement().complement().raw, "=1.0.0");
+
+    Ok(())
+}
+
+#[test]
+fn test_normalize() -> Result<()> {
+    let ra = Range::parse(">=1<3||^2||=2.5.0")?;
+    assert_eq!(ra.set.len(), 3);
+    assert_eq!(ra.normalize().raw, ">=1.0.0<3.0.0");
+    assert_eq!(ra.normalize().set.len(), 1);
+
+    let rb = Range::parse("=3,^2,=1.5,~1.2")?;
+    assert_eq!(rb.normalize().raw, ">=1.2.0<1.3.0,=1.5.0,>=2.0.0<=3.0.0");
+
+    let rc = Range::parse("<2,>=1.5")?;
+    assert_eq!(rc.normalize(), Range::any());
+    let rf = Range {
+        raw: "^1,*".into(),
+        set: vec![Constraint::parse("^1")?, Constraint::Any],
+    };
+    assert_eq!(rf.normalize(), Range::any());
+
+    let rd = Range::parse("~1.2,~1.3,~1.4")?;
+    assert_eq!(rd.normalize().to_string(), ">=1.2<1.5");
+
+    // Already normal ranges are untouched.
+    let re = Range::parse("^1,^3")?;
+    assert_eq!(re.normalize(), re);
+    assert_eq!(re.normalize().normalize(), re.normalize());
+
+    // Only exclusions means everything else, with no `*` beside them.
+    let any = Range::any();
+    let exclusions = [
+        Range::parse("!=1.4.7")?.normalize(),
+        Range::parse("=1.0.0")?.complement(),
+        Range::parse("<1")?.union(&Range::parse(">1")?),
+        any.difference(&Range::parse("=1.0.0")?),
+    ];
+    for r in exclusions {
+        assert!(
+            r.set.iter().all(|c| matches!(c, Constraint::Exclude(_))),
+            "{r}"
+        );
+        assert!(Range::parse(&r.raw)?.equivalent(&r), "{}", r.raw);
+        assert!(Range::parse(&r.to_string())?.equivalent(&r), "{r}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_equivalent() -> Result<()> {
+    let ra = Range::parse(">=1<3||^2||=2.5.0")?;
+    let rb = Range::parse(">=1<3")?;
+    let rc = Range::parse("^1,^2")?;
+    let rd = Range::parse("^1")?;
+
+    assert_ne!(ra, rb);
+    assert!(ra.equivalent(&rb));
+    assert!(rb.equivalent(&rc));
+    assert!(!rc.equivalent(&rd));
+    assert!(Range::parse("=1.2")?.equivalent(&Range::parse("=1.2.0")?));
+    assert!(Range::parse("<1,>=1")?.equivalent(&Range::any()));
+    assert!(!Range::parse(">=1<2")?.equivalent(&Range::parse(">=1<2,=2")?));
+
+    Ok(())
+}
+
+#[test]
+fn test_subset() -> Result<()> {
+    let ra = Range::parse("~1.2")?;
+    let rb = Range::parse("^1")?;
+    let rc = Range::parse("^1.3")?;
+    let rd = Range::parse("=1.2.7,=1.3")?;
+    let re = Range::parse(">=1.2<1.3,>=1.3<1.4")?;
+
+    assert!(ra.is_subset_of(&rb));
+    assert!(!rb.is_subset_of(&ra));
+    assert!(rb.is_superset_of(&ra));
+    assert!(!ra.is_superset_of(&rb));
+    assert!(!ra.is_subset_of(&rc));
+    assert!(rd.is_subset_of(&rb));
+    assert!(!rd.is_subset_of(&ra));
+    assert!(rd.is_subset_of(&re));
+    assert!(Range::parse("~1.2.5")?.is_subset_of(&re));
+    assert!(Range::parse(">=1.2.5<1.3.5")?.is_subset_of(&re));
+
+    // Every range is a subset of itself and of `*`.
+    assert!(rb.is_subset_of(&rb));
+    assert!(rb.is_subset_of(&Range::any()));
+    assert!(!Range::any().is_subset_of(&rb));
+    assert!(Range::parse(">=0")?.is_subset_of(&Range::any()));
+
+    Ok(())
+}
+
+#[test]
+fn test_disjoint() -> Result<()> {
+    let ra = Range::parse("~1.2")?;
+    let rb = Range::parse("~1.3")?;
+    let rc = Range::parse("^1")?;
+    let rd = Range::parse("=1.3")?;
+    let re = Range::parse("<1.3")?;
+
+    assert!(ra.is_disjoint(&rb));
+    assert!(!ra.overlaps(&rb));
+    assert!(ra.overlaps(&rc));
+    assert!(!ra.is_disjoint(&rc));
+    assert!(rb.overlaps(&rd));
+    assert!(re.is_disjoint(&rd));
+    assert!(rd.is_disjoint(&re));
+    assert!(Range::any().overlaps(&rd));
+
+    Ok(())
+}
+
 #[test]
 fn test_at() -> Result<()> {
     let ra = Range::parse(">=1.0<1.1")?;
@@ -266,6 +661,78 @@ fn test_at() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_exclude() -> Result<()> {
+    let ra = Range::parse("^1.4,!=1.4.7")?;
+    let rb = Range::parse("!=1")?;
+
+    assert!(ra.satisfies(&Semver::parse("1.4.6")?));
+    assert!(!ra.satisfies(&Semver::parse("1.4.7")?));
+    assert!(ra.satisfies(&Semver::parse("1.9.0")?));
+    assert!(!ra.satisfies(&Semver::parse("2.0.0")?));
+    assert_eq!(ra.to_string(), "^1.4,!=1.4.7");
+    assert_eq!(ra.raw, "^1.4,!=1.4.7");
+
+    let versions = ["1.4.6", "1.4.7"]
+        .iter()
+        .map(|v| Semver::parse(v))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(ra.max(&versions).map(|v| v.raw), Some("1.4.6".into()));
+
+    // Exclusions alone take versions away from `*`.
+    assert!(rb.satisfies(&Semver::parse("2.0.0")?));
+    assert!(!rb.satisfies(&Semver::parse("1.0.0")?));
+    assert!(rb.equivalent(&Range::any().difference(&Range::parse("=1")?)));
+
+    assert_eq!(
+        ra.intersect(&Range::parse("~1.4")?)?.raw,
+        ">=1.4.0<1.5.0,!=1.4.7"
+    );
+    assert!(ra
+        .intersect(&Range::parse("=1.4.7")?)
+        .is_ok_and(|r| r.is_empty()));
+
+    assert!(Range::parse("!=").is_err());
+    assert!(Range::parse("!1.2").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_bounds() -> Result<()> {
+    let ra = Range::parse(">1.2")?;
+    let rb = Range::parse("<=2")?;
+    let rc = Range::parse(">1<=2")?;
+    let rd = Range::parse(">=1<=1.4")?;
+    let v = |v: &str| Semver::parse(v);
+
+    assert!(!ra.satisfies(&v("1.2")?));
+    assert!(ra.satisfies(&v("1.2.1")?));
+    assert!(rb.satisfies(&v("2.0.0")?));
+    assert!(!rb.satisfies(&v("2.0.1")?));
+    assert!(!rc.satisfies(&v("1")?));
+    assert!(rc.satisfies(&v("2")?));
+    assert!(rd.satisfies(&v("1")?));
+    assert!(rd.satisfies(&v("1.4")?));
+
+    assert_eq!(ra.raw(), ">1.2.0");
+    assert_eq!(rb.raw(), ">=0.0.0<=2.0.0");
+    assert_eq!(rc.to_string(), ">1<=2");
+    assert_eq!(rd.to_string(), ">=1<=1.4");
+    // The shorthands are for half-open ranges only.
+    assert_eq!(Range::parse(">=1<=2")?.to_string(), ">=1<=2");
+    assert_eq!(Range::parse(">1<2")?.to_string(), ">1<2");
+    assert_eq!(Range::parse(">=1<2")?.to_string(), "^1");
+
+    assert_eq!(ra.intersect(&rb)?.raw, ">1.2.0<=2.0.0");
+    assert_eq!(rc.intersect(&Range::parse("^2")?)?.raw, "=2.0.0");
+    assert!(rc.intersect(&Range::parse("<=1")?)?.is_empty());
+    assert_eq!(rb.complement().raw, ">2.0.0");
+    assert!(Range::parse(">1<=1").is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_display() -> Result<()> {
     let ra = Range::parse("^3.7")?;
@@ -294,6 +761,22 @@ fn test_display() -> Result<()> {
     let rk = Range::parse(">=5.0.0<Infinity.Infinity.Infinity")?;
     assert_eq!(rk.to_string(), ">=5");
 
+    // Zeros in a prerelease aren't trailing components.
+    for input in [
+        "^1.0.0-rc.0",
+        "~1.2.0-beta.0",
+        "~0.3.0-alpha.0",
+        "^1.0.0-rc.0,^3",
+    ] {
+        let r = Range::parse(input)?;
+        assert!(
+            Range::parse(&r.to_string())?.equivalent(&r),
+            "{input} as {r}"
+        );
+    }
+    assert_eq!(Range::parse("^1.0.0-rc.0")?.to_string(), "^1-rc.0");
+    assert_eq!(Range::parse("~1.2.0-beta.0")?.to_string(), "~1.2-beta.0");
+
     Ok(())
 }
 
@@ -342,9 +825,17 @@ fn test_serde() -> Result<()> {
     assert_tokens(&ra, &[Token::Str("^3.7")]);
     assert_tokens(&rb, &[Token::Str("=3.11")]);
     assert_tokens(&rc, &[Token::Str("^3.9")]);
+    assert_tokens(&Range::parse("^1,!=1.2.3")?, &[Token::Str("^1,!=1.2.3")]);
+    let rf = Range::parse("=1")?.complement();
+    let json = serde_json::to_string(&rf)?;
+    assert!(serde_json::from_str::<Range>(&json)?.equivalent(&rf));
 
     let rd = serde_json::from_str::<Range>("\"your mom\"");
     assert!(rd.is_err());
 
+    assert_tokens(&Range::none(), &[Token::Str("<0")]);
+    let re = serde_json::from_str::<Range>("\"\"")?;
+    assert!(re.is_empty());
+
     Ok(())
 }
@@ -0,0 +1,129 @@
+use crate::{
+    semver::{dialect::rpm::Version, Semver},
+    Diagnostic, Error, ErrorKind, Part,
+};
+use anyhow::Result;
+use std::cmp::Ordering;
+
+// From rpm's `tests/rpmvercmp.at`.
+#[test]
+fn test_compare() -> Result<()> {
+    let cases = [
+        ("1.0", "1.0", Ordering::Equal),
+        ("1.0", "2.0", Ordering::Less),
+        ("2.0.1", "2.0.1", Ordering::Equal),
+        ("2.0", "2.0.1", Ordering::Less),
+        ("2.0.1a", "2.0.1a", Ordering::Equal),
+        ("2.0.1a", "2.0.1", Ordering::Greater),
+        ("5.5p1", "5.5p1", Ordering::Equal),
+        ("5.5p1", "5.5p2", Ordering::Less),
+        ("5.5p10", "5.5p10", Ordering::Equal),
+        ("5.5p1", "5.5p10", Ordering::Less),
+        ("10xyz", "10.1xyz", Ordering::Less),
+        ("xyz10", "xyz10", Ordering::Equal),
+        ("xyz10", "xyz10.1", Ordering::Less),
+        ("xyz.4", "xyz.4", Ordering::Equal),
+        ("xyz.4", "8", Ordering::Less),
+        ("xyz.4", "2", Ordering::Less),
+        ("5.5p2", "5.6p1", Ordering::Less),
+        ("5.6p1", "6.5p1", Ordering::Less),
+        ("6.0.rc1", "6.0", Ordering::Greater),
+        ("10b2", "10a1", Ordering::Greater),
+        ("1.0aa", "1.0aa", Ordering::Equal),
+        ("1.0a", "1.0aa", Ordering::Less),
+        ("10.0001", "10.0001", Ordering::Equal),
+        ("10.0001", "10.1", Ordering::Equal),
+        ("10.0001", "10.0039", Ordering::Less),
+        ("4.999.9", "5.0", Ordering::Less),
+        ("20101121", "20101121", Ordering::Equal),
+        ("20101121", "20101122", Ordering::Less),
+        ("2_0", "2_0", Ordering::Equal),
+        ("2.0", "2_0", Ordering::Equal),
+        ("a", "a", Ordering::Equal),
+        ("a+", "a+", Ordering::Equal),
+        ("a+", "a_", Ordering::Equal),
+        ("+a", "_a", Ordering::Equal),
+        ("1.0~rc1", "1.0~rc1", Ordering::Equal),
+        ("1.0~rc1", "1.0", Ordering::Less),
+        ("1.0~rc1", "1.0~rc2", Ordering::Less),
+        ("1.0~rc1~git123", "1.0~rc1~git123", Ordering::Equal),
+        ("1.0~rc1~git123", "1.0~rc1", Ordering::Less),
+        ("1.0^", "1.0^", Ordering::Equal),
+        ("1.0^", "1.0", Ordering::Greater),
+        ("1.0^git1", "1.0", Ordering::Greater),
+        ("1.0^git1", "1.0^git2", Ordering::Less),
+        ("1.0^git1", "1.01", Ordering::Less),
+        ("1.0^20160101", "1.0.1", Ordering::Less),
+        ("1.0^20160101^git1", "1.0^20160101", Ordering::Greater),
+        ("1.0~rc1^git1", "1.0~rc1", Ordering::Greater),
+        ("1.0^git1~pre", "1.0^git1", Ordering::Less),
+        ("1.0-1", "1.0-2", Ordering::Less),
+        ("1:1.0", "2.0", Ordering::Greater),
+    ];
+
+    for (a, b, expected) in cases {
+        let (a_, b_) = (Version::parse(a)?, Version::parse(b)?);
+        assert_eq!(a_.cmp(&b_), expected, "{a} <=> {b}");
+        assert_eq!(b_.cmp(&a_), expected.reverse(), "{b} <=> {a}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    let cases = [
+        ("", ErrorKind::EmptyIdentifier(Part::Component), 0..0),
+        ("1.0-1-2", ErrorKind::UnknownCharacter('-'), 3..4),
+        ("1.0-", ErrorKind::EmptyIdentifier(Part::Revision), 4..4),
+        ("x:1.0", ErrorKind::UnknownCharacter('x'), 0..1),
+    ];
+
+    for (input, kind, span) in cases {
+        assert_eq!(
+            Version::parse(input).map(|v| v.raw),
+            Err(Error::Parse(Diagnostic::new(kind, span, input))),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn test_semver() -> Result<()> {
+    let v = Version::from_semver(&Semver::parse("1.2.3-beta.2")?)?;
+    assert_eq!(v.to_string(), "1.2.3~beta.2");
+    assert_eq!(v.to_semver()?.raw, "1.2.3-beta.2");
+
+    assert!(Version::from_semver(&Semver::parse("1.2.3+build")?).is_err());
+    assert!(Version::from_semver(&Semver::parse("1.0.0-x-y")?).is_err());
+    for rpm in ["1:1.0", "1.0-1", "1.0^git1", "1_0"] {
+        assert!(Version::parse(rpm)?.to_semver().is_err(), "{rpm}");
+    }
+
+    // Conversion keeps SemVer 2.0 §11's order, but for a number against
+    // letters, which `rpmvercmp` ranks the other way.
+    let sorted = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+        "1.0.1",
+    ];
+    let rpm = sorted
+        .iter()
+        .map(|v| Version::from_semver(&Semver::parse(v)?))
+        .collect::<crate::Result<Vec<_>>>()?;
+    for pair in rpm.windows(2) {
+        if pair[0].to_string() == "1.0.0~alpha.1" {
+            assert!(pair[0] > pair[1], "{} > {}", pair[0], pair[1]);
+        } else {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    Ok(())
+}
@@ -1,7 +1,15 @@
-use crate::semver::{bump::SemverComponent, Semver};
+use crate::semver::{
+    bump::SemverComponent, compare::BuildSensitive, prerelease::Identifier, Semver,
+};
+use crate::{Diagnostic, Error, ErrorKind, Part};
 use anyhow::Result;
 #[cfg(feature = "serde")]
 use serde_test::{assert_tokens, Token};
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    hash::{DefaultHasher, Hash, Hasher},
+};
 
 #[test]
 fn test_parse() -> Result<()> {
@@ -43,6 +51,60 @@ fn test_parse() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_parse_strict() -> Result<()> {
+    assert_eq!(Semver::parse_strict("1.2.3")?.raw, "1.2.3");
+    assert_eq!(Semver::parse_strict("0.0.0")?.raw, "0.0.0");
+    assert_eq!(
+        Semver::parse_strict("1.0.0-alpha-a.b-c.0+build.01-x")?.raw,
+        "1.0.0-alpha-a.b-c.0+build.01-x"
+    );
+    assert_eq!(Semver::parse_strict("1.0.0-0a.1")?.prerelease.len(), 2);
+
+    let fails = [
+        ("v1.2.3", ErrorKind::Prefix, 0..1),
+        ("1.2", ErrorKind::ComponentCount(2), 0..3),
+        ("1.2.3.4", ErrorKind::ComponentCount(4), 0..7),
+        ("1.1.1q", ErrorKind::UnknownCharacter('q'), 5..6),
+        ("01.2.3", ErrorKind::LeadingZero(Part::Major), 0..2),
+        ("1.02.3", ErrorKind::LeadingZero(Part::Minor), 2..4),
+        ("1..3", ErrorKind::EmptyIdentifier(Part::Minor), 2..2),
+        (
+            "1.2.99999999999999999999",
+            ErrorKind::Overflow(Part::Patch),
+            4..24,
+        ),
+        ("1.2.3-", ErrorKind::EmptyIdentifier(Part::Prerelease), 6..6),
+        (
+            "1.2.3-alpha..1",
+            ErrorKind::EmptyIdentifier(Part::Prerelease),
+            12..12,
+        ),
+        (
+            "1.2.3-alpha.01",
+            ErrorKind::LeadingZero(Part::Prerelease),
+            12..14,
+        ),
+        ("1.2.3-alpha_1", ErrorKind::UnknownCharacter('_'), 11..12),
+        ("1.2.3+", ErrorKind::EmptyIdentifier(Part::Build), 6..6),
+        ("1.2.3-rc+a+b", ErrorKind::UnknownCharacter('+'), 10..11),
+    ];
+    for (input, kind, span) in fails {
+        let err = Semver::parse_strict(input).unwrap_err();
+        assert_eq!(
+            err,
+            Error::Parse(Diagnostic::new(kind, span, input)),
+            "{input}"
+        );
+    }
+
+    // The lenient parser is unchanged.
+    assert!(Semver::parse("v1.2.3").is_ok());
+    assert!(Semver::parse("1.1.1q").is_ok());
+
+    Ok(())
+}
+
 #[test]
 fn test_compare() -> Result<()> {
     let a = Semver::parse("1.2.3")?;
@@ -90,11 +152,9 @@ fn test_compare() -> Result<()> {
     assert!(f.lt(&g)); // 1.2.3-alpha.1 < 1.2.3-alpha.2
     assert!(g.gt(&f)); // 1.2.3-alpha.2 > 1.2.3-alpha.1
     assert!(g.lt(&h)); // 1.2.3-alpha.2 < 1.2.3-beta.1
-    assert!(f.lt(&i)); // 1.2.3-alpha.1 < 1.2.3-alpha.1+8ec0834
-    assert!(i.gt(&f)); // 1.2.3-alpha.1+8ec0834 > 1.2.3-alpha.1
+    assert!(f.eq(&i)); // 1.2.3-alpha.1 == 1.2.3-alpha.1+8ec0834
     assert!(i.eq(&i)); // 1.2.3-alpha.1+8ec0834 == 1.2.3-alpha.1+8ec0834
-    assert!(i.gt(&j)); // 1.2.3-alpha.1+8ec0834 > 1.2.3-alpha.1+7ec0834
-    assert!(j.lt(&i)); // 1.2.3-alpha.1+7ec0834 < 1.2.3-alpha.1+8ec0834
+    assert!(i.eq(&j)); // 1.2.3-alpha.1+8ec0834 == 1.2.3-alpha.1+7ec0834
     assert!(i.lt(&k)); // 1.2.3-alpha.1+8ec0834 < 1.2.3-alpha.2+7ec0834
     assert!(j.lt(&k)); // 1.2.3-alpha.1+7ec0834 < 1.2.3-alpha.2+7ec0834
 
@@ -106,6 +166,119 @@ fn test_compare() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_prerelease_precedence() -> Result<()> {
+    // The precedence chain from SemVer 2.0 §11.
+    let chain = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+    ]
+    .iter()
+    .map(|s| Semver::parse(s))
+    .collect::<crate::Result<Vec<_>>>()?;
+
+    for (i, a) in chain.iter().enumerate() {
+        for (j, b) in chain.iter().enumerate() {
+            assert_eq!(a.cmp(b), i.cmp(&j), "{a} vs {b}");
+        }
+    }
+
+    let mut shuffled = chain.clone();
+    shuffled.reverse();
+    shuffled.sort();
+    assert_eq!(shuffled, chain);
+
+    assert!(Semver::parse("1.0.0-rc.10")?.gt(&Semver::parse("1.0.0-rc.2")?));
+    assert!(Semver::parse("1.0.0-alpha.10")?.gt(&Semver::parse("1.0.0-alpha.9")?));
+    assert!(Semver::parse("1.0.0-1")?.lt(&Semver::parse("1.0.0-a")?));
+    assert!(Semver::parse("1.0.0-Z")?.lt(&Semver::parse("1.0.0-a")?));
+
+    Ok(())
+}
+
+#[test]
+fn test_prerelease_identifiers() -> Result<()> {
+    let a = Semver::parse("1.2.3-rc.10.0.x-y")?;
+    assert_eq!(
+        a.prerelease,
+        [
+            Identifier::AlphaNumeric("rc".into()),
+            Identifier::Numeric(10),
+            Identifier::Numeric(0),
+            Identifier::AlphaNumeric("x-y".into()),
+        ]
+    );
+    assert_eq!(a.raw, "1.2.3-rc.10.0.x-y");
+
+    // Leading zeros and overflowing values aren't numeric, so they round-trip.
+    assert_eq!(
+        Identifier::parse("01"),
+        Identifier::AlphaNumeric("01".into())
+    );
+    assert!(!Identifier::parse("99999999999999999999").is_numeric());
+    assert!(Identifier::from("7").is_numeric());
+    assert_eq!(Identifier::from(7).to_string(), "7");
+    assert_eq!(Semver::parse("1.2-alpha.01")?.raw, "1.2.0-alpha.01");
+
+    Ok(())
+}
+
+#[test]
+fn test_build_metadata() -> Result<()> {
+    let a = Semver::parse("1.2.3+a")?;
+    let b = Semver::parse("1.2.3+b")?;
+    let c = Semver::parse("1.2.3")?;
+    let d = Semver::parse("1.2.3-alpha.1+build.10")?;
+    let e = Semver::parse("1.2.3-alpha.1+build.9")?;
+
+    // Build metadata doesn't affect precedence...
+    assert_eq!(a, b);
+    assert_eq!(a.cmp(&c), Ordering::Equal);
+    assert_eq!(hash(&a), hash(&b));
+    assert_eq!(hash(&a), hash(&c));
+
+    // ...unless asked for explicitly.
+    assert!(!a.eq_exact(&b));
+    assert!(a.eq_exact(&a));
+    assert_eq!(a.cmp_with_build(&b), Ordering::Less);
+    assert_eq!(c.cmp_with_build(&a), Ordering::Less);
+    assert_eq!(d.cmp_with_build(&e), Ordering::Greater);
+    assert_eq!(c.cmp_with_build(&d), Ordering::Greater);
+
+    let set = [a.clone(), b.clone(), c.clone()]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!(set.len(), 1);
+
+    let set = [a.clone(), b.clone(), c.clone(), a.clone()]
+        .into_iter()
+        .map(BuildSensitive::from)
+        .collect::<HashSet<_>>();
+    assert_eq!(set.len(), 3);
+    assert!(BuildSensitive(a) < BuildSensitive(b));
+
+    // Hash agrees with Eq across padding and calver.
+    assert_eq!(hash(&Semver::parse("0.2")?), hash(&Semver::parse("0.2.0")?));
+    assert_eq!(
+        hash(&Semver::parse("2023.3")?),
+        hash(&Semver::parse("2023.3.0.0")?)
+    );
+
+    Ok(())
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut h = DefaultHasher::new();
+    t.hash(&mut h);
+    h.finish()
+}
+
 #[test]
 fn test_sort() -> Result<()> {
     let a = Semver::parse("1.2.3")?;
@@ -160,6 +333,76 @@ fn test_bump() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_bump_prerelease() -> Result<()> {
+    let bump = |v: &str, which: &str, preid: Option<&str>| -> Result<String> {
+        Ok(Semver::parse(v)?
+            .bump_with(&SemverComponent::parse(which)?, preid)?
+            .raw)
+    };
+
+    assert_eq!(bump("1.2.3", "premajor", None)?, "2.0.0-0");
+    assert_eq!(bump("1.2.3", "premajor", Some("rc"))?, "2.0.0-rc.0");
+    assert_eq!(bump("1.2.3", "preminor", Some("alpha"))?, "1.3.0-alpha.0");
+    assert_eq!(bump("1.2.3", "prepatch", None)?, "1.2.4-0");
+    assert_eq!(bump("1.2.3", "prerelease", Some("rc"))?, "1.2.4-rc.0");
+    assert_eq!(bump("1.2.3-rc.9", "prerelease", None)?, "1.2.3-rc.10");
+    assert_eq!(bump("1.2.3-rc.9", "prerelease", Some("rc"))?, "1.2.3-rc.10");
+    assert_eq!(bump("1.2.3-rc.1.a", "prerelease", None)?, "1.2.3-rc.2.a");
+    assert_eq!(
+        bump("1.2.3-alpha.3", "prerelease", Some("beta"))?,
+        "1.2.3-beta.0"
+    );
+    assert_eq!(bump("1.2.3-beta", "prerelease", None)?, "1.2.3-beta.0");
+    assert_eq!(bump("1.2.3-rc.1+b40", "release", None)?, "1.2.3");
+    assert_eq!(bump("1.2.3", "release", None)?, "1.2.3");
+    assert!(bump("1.2.3", "prerelease", Some("r c")).is_err());
+
+    // The `Pre*` bumps order after what they bump from.
+    let a = Semver::parse("1.2.3-rc.9")?;
+    assert!(a.bump(&SemverComponent::Prerelease)?.gt(&a));
+    assert!(a.bump(&SemverComponent::Release)?.gt(&a));
+
+    Ok(())
+}
+
+#[test]
+fn test_bump_shape() -> Result<()> {
+    let bump = |v: &str, which: &str| -> Result<String> {
+        Ok(Semver::parse(v)?.bump(&SemverComponent::parse(which)?)?.raw)
+    };
+
+    assert_eq!(bump("1.2.3.4", "3")?, "1.2.3.5");
+    assert_eq!(bump("1.2.3.4", "1")?, "1.3.0.0");
+    assert_eq!(bump("1.2.3.4", "patch")?, "1.2.4.0");
+    assert_eq!(bump("1.2.3.4.5", "major")?, "2.0.0.0.0");
+    assert_eq!(bump("1.2.3", "3")?, "1.2.3.1");
+    assert_eq!(bump("1.2", "patch")?, "1.2.1");
+
+    assert_eq!(bump("1.1.1q", "letter")?, "1.1.1r");
+    assert_eq!(bump("1.1.1q", "3")?, "1.1.1r");
+    assert_eq!(bump("1.1.1q", "patch")?, "1.1.2");
+    assert_eq!(bump("1.1.1", "letter")?, "1.1.1a");
+    assert_eq!(bump("9e", "letter")?, "9f");
+    assert_eq!(bump("1.1.1q-rc.1", "prerelease")?, "1.1.1q-rc.2");
+    assert_eq!(bump("1.1.1q-rc.1", "release")?, "1.1.1q");
+    assert!(bump("1.1.1z", "letter").is_err());
+    assert!(bump("1.1.1q", "4").is_err());
+    assert_eq!(bump("1", "patch")?, "1.0.1");
+    assert_eq!(bump("1.2", "3")?, "1.2.0.1");
+    for i in ["4", "6", "10000000000", "18446744073709551615"] {
+        let which = SemverComponent::parse(i)?;
+        let e = Semver::parse("1.2.3")?.bump(&which).unwrap_err();
+        assert!(matches!(e, Error::Bump(_)), "{i}");
+    }
+
+    let a = Semver::parse("1.1.1q")?;
+    assert!(a.bump(&SemverComponent::Letter)?.gt(&a));
+    assert_eq!(SemverComponent::from("3"), SemverComponent::Index(3));
+
+    Ok(())
+}
+
 #[test]
 fn test_infinty() {
     let inf = Semver::infinty();